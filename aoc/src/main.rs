@@ -0,0 +1,254 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use clap::{Args, Parser, Subcommand};
+use sdk::anyhow::bail;
+use sdk::answers::Answers;
+use sdk::bench::{format_table, BenchConfig, BenchReport};
+use sdk::fetch::{fetch_input, Client};
+use sdk::frames::{set_output, FrameOutput, DEFAULT_FRAME_DELAY};
+use sdk::input::{read_stdin, Inputs};
+use sdk::solution::Registration;
+use sdk::submit::submit;
+use sdk::*;
+
+#[derive(Debug, Parser)]
+#[command(about = "Run Advent of Code solutions")]
+struct Cli {
+    /// Directory to read inputs from, overriding AOC_INPUTS_DIR
+    #[arg(long, global = true)]
+    inputs_dir: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run a single day, or `all` registered days
+    Run {
+        day: DaySelection,
+        #[command(flatten)]
+        args: RunArgs,
+    },
+    /// Download the real input for a day, or `all` registered days, into the inputs directory
+    Fetch {
+        day: DaySelection,
+    },
+    /// Submit an answer for a day and part. Without an explicit answer, the real input is solved
+    /// and its output submitted
+    Submit {
+        day: u8,
+        part: Part,
+        answer: Option<String>,
+    },
+    /// List registered days and the inputs available for each
+    List,
+}
+
+#[derive(Debug, Args)]
+struct RunArgs {
+    /// Only run this part (1 or 2)
+    #[arg(long)]
+    part: Option<Part>,
+    /// Only run against this input (example or real)
+    #[arg(long)]
+    input: Option<InputKind>,
+    /// Read the puzzle input from stdin instead of the inputs directory
+    #[arg(long)]
+    stdin: bool,
+    /// Time parsing and each part instead of printing answers
+    #[arg(long)]
+    bench: bool,
+    /// Timed iterations per stage when benchmarking
+    #[arg(long, default_value_t = BenchConfig::default().iterations)]
+    iterations: usize,
+    /// Untimed warm-up iterations per stage when benchmarking
+    #[arg(long, default_value_t = BenchConfig::default().warmup)]
+    warmup: usize,
+    /// Animate simulations that record frames in the terminal
+    #[arg(long, conflicts_with_all = ["bench", "frames_dir"])]
+    animate: bool,
+    /// Delay between animated frames, in milliseconds
+    #[arg(long, default_value_t = DEFAULT_FRAME_DELAY.as_millis() as u64)]
+    frame_delay: u64,
+    /// Write frames recorded by simulations into this directory as numbered text files. A later
+    /// recording with the same name, such as the real input after the example, overwrites them
+    #[arg(long, conflicts_with = "bench")]
+    frames_dir: Option<PathBuf>,
+}
+
+impl RunArgs {
+    fn parts(&self) -> &[Part] {
+        self.part.as_ref().map(std::slice::from_ref).unwrap_or(Part::ALL)
+    }
+
+    fn bench_config(&self) -> Option<BenchConfig> {
+        self.bench.then_some(BenchConfig { warmup: self.warmup, iterations: self.iterations })
+    }
+
+    /// Frame output requested on the command line, if any; otherwise `AOC_FRAMES` applies.
+    fn frame_output(&self) -> Option<FrameOutput> {
+        if self.animate {
+            Some(FrameOutput::Ansi { delay: Duration::from_millis(self.frame_delay) })
+        } else {
+            self.frames_dir.clone().map(|dir| FrameOutput::Files { dir })
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum DaySelection {
+    All,
+    Day(u8),
+}
+
+impl FromStr for DaySelection {
+    type Err = TextError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(DaySelection::All),
+            day => day
+                .parse()
+                .map(DaySelection::Day)
+                .map_err(|_| TextError(format!("Expected a day number or `all`, got {day}"))),
+        }
+    }
+}
+
+fn registry() -> Vec<Registration> {
+    vec![
+        Registration::new::<day1::Day1>(),
+        Registration::new::<day2::Day2>(),
+        Registration::new::<day3::Day3>(),
+        Registration::new::<day4::Day4>(),
+        Registration::new::<day5::Day5>(),
+        Registration::new::<day6::Day6>(),
+        Registration::new::<day7::Day7>(),
+        Registration::new::<day8::Day8>(),
+        Registration::new::<day9::Day9>(),
+        Registration::new::<day10::Day10>(),
+        Registration::new::<day11::Day11>(),
+        Registration::new::<day12::Day12>(),
+    ]
+}
+
+fn main() -> anyhow::Result<()> {
+    init();
+    let cli = Cli::parse();
+    let inputs = match cli.inputs_dir {
+        Some(dir) => Inputs::new(dir),
+        None => Inputs::from_env(),
+    };
+    let registry = registry();
+    match cli.command {
+        Command::Run { day: DaySelection::All, args } => {
+            if let Some(output) = args.frame_output() {
+                set_output(output);
+            }
+            if args.stdin {
+                bail!("--stdin needs a single day, not `all`");
+            }
+            let mut reports = Vec::new();
+            for registration in &registry {
+                reports.extend(run(registration, &inputs, &args, false)?);
+            }
+            print_reports(&reports);
+        }
+        Command::Run { day: DaySelection::Day(day), args } => {
+            if let Some(output) = args.frame_output() {
+                set_output(output);
+            }
+            let Some(registration) = registry.iter().find(|r| r.day == day) else {
+                bail!("Day {day} is not registered");
+            };
+            let reports = if args.stdin {
+                let kind = args.input.unwrap_or(InputKind::Real);
+                run_input(registration, kind, &read_stdin()?, &args, &Answers::default())?
+                    .into_iter()
+                    .collect()
+            } else {
+                run(registration, &inputs, &args, true)?
+            };
+            print_reports(&reports);
+        }
+        Command::Fetch { day } => {
+            let client = Client::from_env()?;
+            let days: Vec<u8> = match day {
+                DaySelection::All => registry.iter().map(|r| r.day).collect(),
+                DaySelection::Day(day) => vec![day],
+            };
+            for day in days {
+                fetch_input(&client, &inputs, day)?;
+            }
+        }
+        Command::Submit { day, part, answer } => {
+            let Some(registration) = registry.iter().find(|r| r.day == day) else {
+                bail!("Day {day} is not registered");
+            };
+            let answer = match answer {
+                Some(answer) => answer,
+                None => registration.solve(InputKind::Real, &inputs.load(day, InputKind::Real)?, part)?,
+            };
+            let outcome = submit(&Client::from_env()?, &inputs, day, part, &answer)?;
+            println!("Day {day} part {part} answer {answer}: {outcome}");
+        }
+        Command::List => {
+            for registration in &registry {
+                let available: Vec<_> = inputs.available(registration.day)
+                    .iter()
+                    .map(|kind| kind.to_string())
+                    .collect();
+                let available = if available.is_empty() { "no inputs".to_owned() } else { available.join(", ") };
+                println!("Day {}: {available}", registration.day);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Runs the selected parts of a day against the selected inputs, returning benchmark reports when
+/// benchmarking. When `strict` is set, a missing input file is an error rather than being skipped.
+fn run(registration: &Registration, inputs: &Inputs, args: &RunArgs, strict: bool) -> anyhow::Result<Vec<BenchReport>> {
+    let kinds = match args.input {
+        Some(kind) => vec![kind],
+        None if strict => InputKind::ALL.to_vec(),
+        None => inputs.available(registration.day),
+    };
+    if kinds.is_empty() {
+        warn!("Day {} has no inputs in {}, skipping", registration.day, inputs.dir().display());
+    }
+    let answers = Answers::load(inputs, registration.day)?;
+    let mut reports = Vec::new();
+    for kind in kinds {
+        if !strict && !inputs.exists(registration.day, kind) {
+            warn!("Day {} has no {kind} input, skipping", registration.day);
+            continue;
+        }
+        let text = inputs.load(registration.day, kind)?;
+        reports.extend(run_input(registration, kind, &text, args, &answers)?);
+    }
+    Ok(reports)
+}
+
+fn run_input(
+    registration: &Registration,
+    kind: InputKind,
+    text: &str,
+    args: &RunArgs,
+    answers: &Answers,
+) -> anyhow::Result<Option<BenchReport>> {
+    match args.bench_config() {
+        Some(config) => {
+            info!("Benchmarking day {} {kind} input", registration.day);
+            registration.bench(kind, text, args.parts(), config).map(Some)
+        }
+        None => registration.run(kind, text, args.parts(), answers).map(|_| None),
+    }
+}
+
+fn print_reports(reports: &[BenchReport]) {
+    if !reports.is_empty() {
+        print!("{}", format_table(reports));
+    }
+}
@@ -0,0 +1,112 @@
+use sdk::winnow::ascii::dec_int;
+use sdk::winnow::combinator::separated;
+use sdk::winnow::error::InputError;
+use sdk::winnow::token::one_of;
+use sdk::*;
+use sdk::dial::{Dial, Turn};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Input = Vec<Turn>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Vec<Turn>> {
+        Ok(separated(0.., parse_turn, '\n').parse_all(input)?)
+    }
+
+    fn part1(turns: &Vec<Turn>) -> anyhow::Result<u64> {
+        go(turns, START, DIAL_SIZE, Count::Landings)
+    }
+
+    fn part2(turns: &Vec<Turn>) -> anyhow::Result<u64> {
+        go(turns, START, DIAL_SIZE, Count::Passes)
+    }
+}
+
+answer_tests!(Day1);
+
+const START: i64 = 50;
+const DIAL_SIZE: u64 = 100;
+
+/// Which moments of pointing at 0 are counted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Count {
+    /// Only rotations that end on 0.
+    Landings,
+    /// Every click that lands on 0, including those in the middle of a rotation.
+    Passes,
+}
+
+fn go(turns: &[Turn], start: i64, dial_size: u64, count: Count) -> anyhow::Result<u64> {
+    let mut dial = Dial::new(dial_size, start)?;
+    let mut zeroes = 0;
+    for turn in turns {
+        let rotation = dial.rotate(*turn);
+        zeroes += match count {
+            Count::Landings => (rotation.to == 0) as u64,
+            Count::Passes => rotation.crossings,
+        };
+        debug!("{rotation}, zero count: {zeroes}");
+    }
+    Ok(zeroes)
+}
+
+fn parse_turn(input: &mut &str) -> winnow::Result<Turn> {
+    (one_of(['L', 'R']), dec_int)
+        .try_map(|(dir, n)| match dir {
+            'L' => Ok(Turn::Left(n)),
+            'R' => Ok(Turn::Right(n)),
+            other => Err(InputError::at(other)),
+        })
+        .parse_next(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zeroes(start: i64, turns: &[Turn], count: Count) -> u64 {
+        go(turns, start, DIAL_SIZE, count).unwrap()
+    }
+
+    #[test]
+    fn counts_full_revolutions() {
+        assert_eq!(zeroes(50, &[Turn::Right(1000)], Count::Passes), 10);
+        assert_eq!(zeroes(50, &[Turn::Left(1000)], Count::Passes), 10);
+        assert_eq!(zeroes(50, &[Turn::Right(1000)], Count::Landings), 0);
+    }
+
+    #[test]
+    fn counts_left_wraps_through_zero() {
+        assert_eq!(zeroes(50, &[Turn::Left(50)], Count::Passes), 1);
+        assert_eq!(zeroes(50, &[Turn::Left(49)], Count::Passes), 0);
+        assert_eq!(zeroes(50, &[Turn::Left(150)], Count::Passes), 2);
+        assert_eq!(zeroes(50, &[Turn::Left(151)], Count::Passes), 2);
+    }
+
+    #[test]
+    fn leaving_zero_to_the_left_is_not_a_pass() {
+        assert_eq!(zeroes(0, &[Turn::Left(5)], Count::Passes), 0);
+        assert_eq!(zeroes(0, &[Turn::Left(99)], Count::Passes), 0);
+        assert_eq!(zeroes(0, &[Turn::Left(100)], Count::Passes), 1);
+        assert_eq!(zeroes(0, &[Turn::Left(250)], Count::Passes), 2);
+    }
+
+    #[test]
+    fn negative_turns_reverse_direction() {
+        assert_eq!(zeroes(50, &[Turn::Left(-150)], Count::Passes), zeroes(50, &[Turn::Right(150)], Count::Passes));
+        assert_eq!(zeroes(10, &[Turn::Right(-10), Turn::Right(-100)], Count::Passes), 2);
+        assert_eq!(zeroes(10, &[Turn::Right(-10), Turn::Right(-100)], Count::Landings), 2);
+    }
+
+    #[test]
+    fn parses_signed_turns() {
+        let turns = Day1::parse("L68\nR-5\nL9223372036854775807").unwrap();
+        assert_eq!(turns, vec![Turn::Left(68), Turn::Right(-5), Turn::Left(i64::MAX)]);
+        assert!(Day1::parse("U5").is_err());
+        assert!(Day1::parse("L").is_err());
+    }
+}
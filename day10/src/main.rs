@@ -1,7 +1,6 @@
+use day10::Day10;
 use sdk::*;
 
 fn main() -> anyhow::Result<()> {
-    init();
-    info!("Hello, world!");
-    Ok(())
+    solution::main::<Day10>()
 }
@@ -1,7 +1,6 @@
+use day11::Day11;
 use sdk::*;
 
 fn main() -> anyhow::Result<()> {
-    init();
-    info!("Hello, world!");
-    Ok(())
+    solution::main::<Day11>()
 }
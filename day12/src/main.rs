@@ -1,7 +1,6 @@
+use day12::Day12;
 use sdk::*;
 
 fn main() -> anyhow::Result<()> {
-    init();
-    info!("Hello, world!");
-    Ok(())
+    solution::main::<Day12>()
 }
@@ -0,0 +1,235 @@
+use std::str::FromStr;
+use sdk::*;
+use sdk::winnow::combinator::separated;
+use sdk::winnow::token::take_while;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Input = Vec<IdRange>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Vec<IdRange>> {
+        Ok(separated(0.., parse_range, ',').parse_all(input)?)
+    }
+
+    fn part1(ranges: &Vec<IdRange>) -> anyhow::Result<u64> {
+        go(ranges, Repeats::Twice, MODE)
+    }
+
+    fn part2(ranges: &Vec<IdRange>) -> anyhow::Result<u64> {
+        go(ranges, Repeats::AtLeastTwice, MODE)
+    }
+}
+
+answer_tests!(Day2);
+
+const MODE: Mode = Mode::Enumerate;
+
+/// How invalid IDs are found.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// Test every ID in every range.
+    BruteForce,
+    /// Generate the repeated-block numbers in each range directly, without visiting the rest.
+    Enumerate,
+}
+
+/// Which repeated-block IDs count as invalid.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Repeats {
+    /// A block repeated exactly twice, e.g. `123123`.
+    Twice,
+    /// A block repeated two or more times, e.g. `123123` or `121212`.
+    AtLeastTwice,
+}
+
+fn go(ranges: &[IdRange], repeats: Repeats, mode: Mode) -> anyhow::Result<u64> {
+    let sum = match mode {
+        Mode::BruteForce => {
+            let is_invalid = match repeats {
+                Repeats::Twice => is_invalid_part_1,
+                Repeats::AtLeastTwice => is_invalid_part_2,
+            };
+            ranges.iter()
+                .flat_map(|r| r.start..=r.end)
+                .filter(|id| is_invalid(*id))
+                .sum()
+        }
+        Mode::Enumerate => {
+            let sum: u128 = ranges.iter().map(|r| sum_repeated(r.start, r.end, repeats)).sum();
+            sum.try_into()?
+        }
+    };
+
+    Ok(sum)
+}
+
+/// Sums the repeated-block numbers in `start..=end`, one digit count at a time. A number with `len`
+/// digits made of a `block`-digit block is that block times `(10^len - 1) / (10^block - 1)`
+/// (`1001` for 3-digit blocks of a 6-digit number, `10101` for 2-digit ones), so the matching
+/// blocks form a contiguous range and their sum is an arithmetic series.
+///
+/// For [Repeats::AtLeastTwice] a number like `222222` repeats blocks of 1, 2 and 3 digits. Every
+/// repeating block length divides `len / p` for some prime `p` dividing `len`, so we take the union
+/// of those maximal block lengths by inclusion–exclusion over the primes: numbers repeating both
+/// `len / p` and `len / q` digit blocks are exactly the ones repeating `len / (p * q)` digit
+/// blocks.
+fn sum_repeated(start: u64, end: u64, repeats: Repeats) -> u128 {
+    let mut sum: i128 = 0;
+    for len in 2..=u64::MAX.ilog10() + 1 {
+        let low = (start as u128).max(10_u128.pow(len - 1));
+        let high = (end as u128).min(10_u128.pow(len) - 1);
+        if low > high {
+            continue;
+        }
+        match repeats {
+            Repeats::Twice => {
+                if len % 2 == 0 {
+                    sum += sum_with_block(low, high, len, len / 2) as i128;
+                }
+            }
+            Repeats::AtLeastTwice => {
+                let primes = prime_factors(len);
+                for subset in 1..1_u32 << primes.len() {
+                    let divisor: u32 = (0..primes.len())
+                        .filter(|i| subset & (1 << i) != 0)
+                        .map(|i| primes[i])
+                        .product();
+                    let block_sum = sum_with_block(low, high, len, len / divisor) as i128;
+                    if subset.count_ones() % 2 == 1 {
+                        sum += block_sum;
+                    } else {
+                        sum -= block_sum;
+                    }
+                }
+            }
+        }
+    }
+    sum as u128
+}
+
+/// Sums the `len`-digit numbers in `low..=high` made of a repeated `block`-digit block.
+fn sum_with_block(low: u128, high: u128, len: u32, block: u32) -> u128 {
+    let multiplier = (10_u128.pow(len) - 1) / (10_u128.pow(block) - 1);
+    let first = low.div_ceil(multiplier).max(10_u128.pow(block - 1));
+    let last = (high / multiplier).min(10_u128.pow(block) - 1);
+    if first > last {
+        return 0;
+    }
+    multiplier * (first + last) * (last - first + 1) / 2
+}
+
+fn prime_factors(mut n: u32) -> Vec<u32> {
+    let mut primes = Vec::new();
+    let mut p = 2;
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            primes.push(p);
+            while n.is_multiple_of(p) {
+                n /= p;
+            }
+        }
+        p += 1;
+    }
+    if n > 1 {
+        primes.push(n);
+    }
+    primes
+}
+
+fn parse_range(input: &mut &str) -> winnow::Result<IdRange> {
+    (
+        take_while(1.., |c: char| c.is_numeric()),
+        '-',
+        take_while(1.., |c: char| c.is_numeric()),
+    ).try_map(|(start, _, end): (&str, char, &str)| -> Result<IdRange, <u64 as FromStr>::Err> {
+        let range = IdRange {
+            start: start.parse()?,
+            end: end.parse()?,
+        };
+        Ok(range)
+    }).parse_next(input)
+}
+
+fn is_invalid_part_1(id: u64) -> bool {
+    let mut digits: u32 = 0;
+    let mut x = id;
+    while x > 0 {
+        digits += 1;
+        x /= 10;
+    }
+    if digits % 2 == 1 {
+        return false;
+    }
+    let pivot = 10_u64.pow(digits / 2);
+    let front = id / pivot;
+    let back = id % pivot;
+    // debug!("id: {id}, front: {front}, back: {back}");
+    front == back
+}
+
+fn is_invalid_part_2(id: u64) -> bool {
+    let id = id.to_string();
+    let len = id.len();
+    let mut test = String::with_capacity(len);
+    let pivot = id.len() / 2;
+    for i in 1..=pivot {
+        if len.is_multiple_of(i) {
+            (0..(len / i)).for_each(|_| test.push_str(&id[..i]));
+            if id == test {
+                return true;
+            } else {
+                test.clear()
+            }
+        }
+    }
+    false
+}
+
+#[derive(Debug, Clone)]
+pub struct IdRange {
+    start: u64,
+    end: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn id_range() -> impl Strategy<Value = IdRange> {
+        (0..10_000_000_000_u64, 0..5_000_u64).prop_map(|(start, len)| IdRange { start, end: start + len })
+    }
+
+    proptest! {
+        #[test]
+        fn enumerate_matches_brute_force(ranges in prop::collection::vec(id_range(), 1..4)) {
+            for repeats in [Repeats::Twice, Repeats::AtLeastTwice] {
+                prop_assert_eq!(
+                    go(&ranges, repeats, Mode::Enumerate).unwrap(),
+                    go(&ranges, repeats, Mode::BruteForce).unwrap(),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn enumerate_matches_brute_force_on_small_ids() {
+        let ranges = [IdRange { start: 0, end: 300_000 }];
+        for repeats in [Repeats::Twice, Repeats::AtLeastTwice] {
+            assert_eq!(go(&ranges, repeats, Mode::Enumerate).unwrap(), go(&ranges, repeats, Mode::BruteForce).unwrap());
+        }
+    }
+
+    #[test]
+    fn enumerates_full_u64_range() {
+        let ranges = [IdRange { start: 0, end: u64::MAX }];
+        let twice = sum_repeated(0, u64::MAX, Repeats::Twice);
+        let at_least_twice = sum_repeated(0, u64::MAX, Repeats::AtLeastTwice);
+        assert!(at_least_twice > twice);
+        assert!(go(&ranges, Repeats::Twice, Mode::Enumerate).is_err());
+    }
+}
@@ -0,0 +1,152 @@
+use sdk::winnow::combinator::separated;
+use sdk::winnow::token::take_while;
+use sdk::*;
+use sdk::anyhow::{bail, Context};
+use sdk::num::Number;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Input = Vec<Battery>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Vec<Battery>> {
+        Ok(separated(0.., parse_battery, '\n').parse_all(input)?)
+    }
+
+    fn part1(batteries: &Vec<Battery>) -> anyhow::Result<u64> {
+        go(batteries, 2, Extreme::Max)
+    }
+
+    fn part2(batteries: &Vec<Battery>) -> anyhow::Result<u64> {
+        go(batteries, 12, Extreme::Max)
+    }
+}
+
+answer_tests!(Day3);
+
+const BASE: u8 = 10;
+
+/// Whether to pick the largest or smallest joltage.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Extreme {
+    Max,
+    Min,
+}
+
+fn go<T: Number>(batteries: &[Battery], cell_count: usize, extreme: Extreme) -> anyhow::Result<T> {
+    batteries.iter().try_fold(T::zero(), |sum, battery| {
+        let Some(cells) = battery.select(cell_count, extreme) else {
+            bail!("Battery {battery:?} has fewer than {cell_count} cells");
+        };
+        let joltage: T = battery.joltage(&cells, BASE).context("Joltage overflowed")?;
+        sum.checked_add(&joltage).context("Total joltage overflowed")
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct Battery(Vec<u8>);
+
+impl Battery {
+    /// Indices of the `cell_count` cells that, kept in order, read as the largest (or smallest)
+    /// number, or `None` if there aren't enough cells.
+    ///
+    /// Walks the cells once with a monotonic stack: a cell pops every worse cell before it while we
+    /// can still afford to drop cells, so each cell is pushed and popped at most once.
+    fn select(&self, cell_count: usize, extreme: Extreme) -> Option<Vec<usize>> {
+        let mut droppable = self.0.len().checked_sub(cell_count)?;
+        let mut stack: Vec<usize> = Vec::with_capacity(self.0.len());
+        for (i, &cell) in self.0.iter().enumerate() {
+            while droppable > 0 && stack.last().is_some_and(|&top| match extreme {
+                Extreme::Max => self.0[top] < cell,
+                Extreme::Min => self.0[top] > cell,
+            }) {
+                stack.pop();
+                droppable -= 1;
+            }
+            stack.push(i);
+        }
+        stack.truncate(cell_count);
+        debug!("Battery: {:?}, selected: {stack:?}", self.0);
+        Some(stack)
+    }
+
+    /// The number read from the cells at `indices` in the given base, or `None` if it doesn't fit.
+    fn joltage<T: Number>(&self, indices: &[usize], base: u8) -> Option<T> {
+        let base = T::from(base);
+        indices.iter().try_fold(T::zero(), |joltage, &i| joltage.checked_mul(&base)?.checked_add(&T::from(self.0[i])))
+    }
+}
+
+fn parse_battery(input: &mut &str) -> winnow::Result<Battery> {
+    take_while(1.., |c: char| c.is_numeric())
+        .map(|s: &str| {
+            Battery(
+                s.chars()
+                    .map(|c| c.to_digit(BASE.into()).expect("{c} was not a digit") as u8)
+                    .collect(),
+            )
+        })
+        .parse_next(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use sdk::num::{BigUint, Integer};
+
+    /// Tries every subsequence of `cell_count` cells.
+    fn brute_force(battery: &Battery, cell_count: usize, extreme: Extreme) -> Option<u128> {
+        let len = battery.0.len();
+        let values = (0_u32..1 << len)
+            .filter(|mask| mask.count_ones() as usize == cell_count)
+            .map(|mask| {
+                let indices: Vec<_> = (0..len).filter(|i| mask & (1 << i) != 0).collect();
+                battery.joltage::<u128>(&indices, BASE).unwrap()
+            });
+        match extreme {
+            Extreme::Max => values.max(),
+            Extreme::Min => values.min(),
+        }
+    }
+
+    fn joltage(battery: &Battery, cell_count: usize, extreme: Extreme) -> Option<u128> {
+        let indices = battery.select(cell_count, extreme)?;
+        assert!(indices.windows(2).all(|pair| pair[0] < pair[1]), "indices {indices:?} out of order");
+        battery.joltage(&indices, BASE)
+    }
+
+    proptest! {
+        #[test]
+        fn selection_matches_brute_force(cells in prop::collection::vec(0..10_u8, 0..14), cell_count in 0..16_usize) {
+            let battery = Battery(cells);
+            for extreme in [Extreme::Max, Extreme::Min] {
+                prop_assert_eq!(joltage(&battery, cell_count, extreme), brute_force(&battery, cell_count, extreme));
+            }
+        }
+    }
+
+    #[test]
+    fn picks_indices() {
+        let battery = Battery(vec![8, 1, 8, 1, 8, 1, 9, 1, 1, 1, 1, 2, 1, 1, 1]);
+        assert_eq!(battery.select(2, Extreme::Max), Some(vec![6, 11]));
+        assert_eq!(battery.select(3, Extreme::Min), Some(vec![1, 3, 5]));
+        assert_eq!(battery.select(16, Extreme::Max), None);
+    }
+
+    #[test]
+    fn widens_past_u64() {
+        let battery = Battery(vec![9; 40]);
+        let cells = battery.select(30, Extreme::Max).unwrap();
+        assert_eq!(battery.joltage::<u64>(&cells, BASE), None);
+        assert_eq!(battery.joltage::<u128>(&cells, BASE), Some(10_u128.pow(30) - 1));
+        let cells = battery.select(40, Extreme::Max).unwrap();
+        assert_eq!(battery.joltage::<BigUint>(&cells, BASE), Some(BigUint::from(10_u8).pow(40) - 1_u8));
+        let batteries = [battery];
+        assert!(go::<u64>(&batteries, 30, Extreme::Max).is_err());
+        assert_eq!(go::<Integer>(&batteries, 30, Extreme::Max).unwrap().to_string(), "9".repeat(30));
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+use sdk::*;
+use sdk::frames::FrameRecorder;
+use sdk::grid::{char_grid, Grid};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Input = Grid<bool>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Grid<bool>> {
+        Ok(parse_grid.parse_all(input)?)
+    }
+
+    fn part1(grid: &Grid<bool>) -> anyhow::Result<u64> {
+        go(grid, false)
+    }
+
+    fn part2(grid: &Grid<bool>) -> anyhow::Result<u64> {
+        go(grid, true)
+    }
+}
+
+answer_tests!(Day4);
+
+/// A roll with fewer than this many neighboring rolls can be reached by a forklift.
+const ACCESSIBLE: u8 = 4;
+
+fn go(grid: &Grid<bool>, take: bool) -> anyhow::Result<u64> {
+    let mut frames = FrameRecorder::new(if take { "day4-part2" } else { "day4-part1" });
+    let removal = remove_rolls(grid, if take { usize::MAX } else { 1 }, &mut frames);
+    frames.finish()?;
+    debug!("removed per round: {:?}", removal.rounds);
+    debug!("final grid:\n{}", render(&removal.grid, &HashSet::new()));
+
+    Ok(removal.rounds.iter().sum())
+}
+
+pub struct Removal {
+    /// Rolls removed in each round, in order. Stops at the first round that removes nothing.
+    pub rounds: Vec<u64>,
+    /// The grid once no more rolls could be removed, or after the last round run.
+    pub grid: Grid<bool>,
+}
+
+/// Removes every accessible roll at once, round after round, for up to `max_rounds` rounds,
+/// recording a frame per round with the rolls it removes marked.
+///
+/// Neighbor counts are kept up to date as rolls go, and a round only re-examines the rolls next
+/// to ones removed in the round before, so the whole simulation is linear in the grid size.
+fn remove_rolls(grid: &Grid<bool>, max_rounds: usize, frames: &mut FrameRecorder) -> Removal {
+    let mut grid = grid.clone();
+    let mut neighbors = grid.map(|_| 0_u8);
+    for (x, y) in grid.positions() {
+        neighbors[(x, y)] = grid.neighbors8(x, y).filter(|&n| grid[n]).count() as u8;
+    }
+    let mut frontier: Vec<_> = grid.positions().filter(|&p| grid[p]).collect();
+    let mut queued = grid.clone();
+    let mut rounds = Vec::new();
+    while rounds.len() < max_rounds {
+        let removed: Vec<_> = frontier
+            .drain(..)
+            .inspect(|&p| queued[p] = false)
+            .filter(|&p| grid[p] && neighbors[p] < ACCESSIBLE)
+            .collect();
+        if removed.is_empty() {
+            break;
+        }
+        frames.record(|| render(&grid, &removed.iter().copied().collect()));
+        for &p in &removed {
+            grid[p] = false;
+        }
+        for &(x, y) in &removed {
+            for n in grid.neighbors8(x, y) {
+                if grid[n] {
+                    neighbors[n] -= 1;
+                    if !queued[n] {
+                        queued[n] = true;
+                        frontier.push(n);
+                    }
+                }
+            }
+        }
+        rounds.push(removed.len() as u64);
+    }
+    frames.record(|| render(&grid, &HashSet::new()));
+    Removal { rounds, grid }
+}
+
+/// Draws rolls as `@`, with those in `removed` as `x`.
+fn render(grid: &Grid<bool>, removed: &HashSet<(usize, usize)>) -> String {
+    grid.render(|position, &roll| match roll {
+        true if removed.contains(&position) => 'x',
+        true => '@',
+        false => '.',
+    })
+}
+
+fn parse_grid(input: &mut &str) -> winnow::Result<Grid<bool>> {
+    char_grid(|c| match c {
+        '.' => Ok(false),
+        '@' => Ok(true),
+        other => Err(TextError(format!("Unexpected character {other:?}"))),
+    })
+    .parse_next(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use sdk::frames::FrameOutput;
+
+    /// The old full-rescan simulation, for comparison.
+    fn rescan(grid: &Grid<bool>) -> Removal {
+        let mut grid = grid.clone();
+        let mut rounds = Vec::new();
+        loop {
+            let accessible: Vec<_> = grid
+                .positions()
+                .filter(|&(x, y)| grid[(x, y)] && grid.neighbors8(x, y).filter(|&n| grid[n]).count() < ACCESSIBLE as usize)
+                .collect();
+            if accessible.is_empty() {
+                return Removal { rounds, grid };
+            }
+            rounds.push(accessible.len() as u64);
+            for p in accessible {
+                grid[p] = false;
+            }
+        }
+    }
+
+    #[test]
+    fn matches_full_rescan() {
+        let input = sdk::input::Inputs::from_env().load(Day4::DAY, InputKind::Example).unwrap();
+        let grid = Day4::parse(&input).unwrap();
+        let expected = rescan(&grid);
+        let removal = remove_rolls(&grid, usize::MAX, &mut FrameRecorder::with_output("test", None));
+        assert_eq!(removal.rounds, expected.rounds);
+        assert_eq!(removal.rounds, vec![13, 12, 7, 5, 2, 1, 1, 1, 1]);
+        assert_eq!(removal.grid, expected.grid);
+        assert_eq!(remove_rolls(&grid, 2, &mut FrameRecorder::with_output("test", None)).rounds, vec![13, 12]);
+    }
+
+    #[test]
+    fn records_a_frame_per_round() {
+        let input = sdk::input::Inputs::from_env().load(Day4::DAY, InputKind::Example).unwrap();
+        let grid = Day4::parse(&input).unwrap();
+        let mut frames = FrameRecorder::with_output("test", Some(FrameOutput::Ansi { delay: Duration::ZERO }));
+        let removal = remove_rolls(&grid, usize::MAX, &mut frames);
+        assert_eq!(frames.frames().len(), removal.rounds.len() + 1);
+        assert_eq!(frames.frames()[0].matches('x').count(), 13);
+        assert_eq!(frames.frames().last().unwrap().matches('x').count(), 0);
+    }
+
+    #[test]
+    fn matches_full_rescan_on_dense_grid() {
+        // Deterministic pseudo-random grid, mostly rolls, so removal cascades over many rounds
+        let mut state: u32 = 0x9e37_79b9;
+        let grid = Grid::filled(60, 40, ()).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state % 10 < 8
+        });
+        let expected = rescan(&grid);
+        let removal = remove_rolls(&grid, usize::MAX, &mut FrameRecorder::with_output("test", None));
+        assert!(expected.rounds.len() > 3);
+        assert_eq!(removal.rounds, expected.rounds);
+        assert_eq!(removal.grid, expected.grid);
+    }
+}
@@ -0,0 +1,56 @@
+use std::ops::RangeInclusive;
+use sdk::*;
+use sdk::interval::IntervalSet;
+use sdk::winnow::ascii::dec_uint;
+use sdk::winnow::combinator::separated;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Input = Inventory;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Inventory> {
+        let inventory = (
+            separated(1.., parse_range, '\n'),
+            "\n\n",
+            separated(1.., dec_uint::<&str, u64, _>, '\n')
+        )
+            .map(|(fresh_ranges, _, ids): (Vec<RangeInclusive<u64>>, _, Vec<u64>)| {
+                Inventory { fresh: fresh_ranges.into_iter().collect(), ids }
+            })
+            .parse_all(input)?;
+        Ok(inventory)
+    }
+
+    fn part1(inventory: &Inventory) -> anyhow::Result<u64> {
+        go(inventory, true)
+    }
+
+    fn part2(inventory: &Inventory) -> anyhow::Result<u64> {
+        go(inventory, false)
+    }
+}
+
+answer_tests!(Day5);
+
+pub struct Inventory {
+    fresh: IntervalSet<u64>,
+    ids: Vec<u64>,
+}
+
+fn go(inventory: &Inventory, only_included_ingredients: bool) -> anyhow::Result<u64> {
+    let fresh = if only_included_ingredients {
+        inventory.ids.iter().filter(|id| inventory.fresh.contains(**id)).count() as u64
+    } else {
+        inventory.fresh.covered_len().try_into()?
+    };
+
+    Ok(fresh)
+}
+
+fn parse_range(input: &mut &str) -> winnow::Result<RangeInclusive<u64>> {
+    (dec_uint, '-', dec_uint).map(|(from, _, to)| from..=to).parse_next(input)
+}
@@ -0,0 +1,354 @@
+use std::ops::Range;
+use sdk::*;
+use sdk::anyhow::Context;
+use sdk::num::{Integer, Number};
+use sdk::parse::Diagnostic;
+use sdk::text;
+
+mod operators;
+
+pub use operators::{Evaluation, Operator, Operators};
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Input = Worksheet;
+    type Output1 = Integer;
+    type Output2 = Integer;
+
+    fn parse(input: &str) -> anyhow::Result<Worksheet> {
+        Ok(Worksheet::parse(input)?)
+    }
+
+    fn part1(worksheet: &Worksheet) -> anyhow::Result<Integer> {
+        go(worksheet, ReadingOrder::Rows, &Operators::standard(), Evaluation::Separately)
+    }
+
+    fn part2(worksheet: &Worksheet) -> anyhow::Result<Integer> {
+        go(worksheet, ReadingOrder::RightToLeft, &Operators::standard(), Evaluation::Separately)
+    }
+}
+
+answer_tests!(Day6);
+
+fn go<T: Number>(worksheet: &Worksheet, order: ReadingOrder, operators: &Operators<T>, evaluation: Evaluation) -> anyhow::Result<T> {
+    let problems = worksheet.problems::<T>(order)?;
+    for problem in &problems {
+        debug!("Problem: {problem:?}");
+    }
+    operators.evaluate(&problems, evaluation)
+}
+
+/// How the digits in a problem's block are read into operands.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReadingOrder {
+    /// A number per row, top row first.
+    Rows,
+    /// A number per column, left column first, with digits read top to bottom.
+    ColumnsTopToBottom,
+    /// A number per column, left column first, with digits read bottom to top.
+    ColumnsBottomToTop,
+    /// A number per column, right column first, with digits read top to bottom. This is how
+    /// cephalopods read.
+    RightToLeft,
+}
+
+impl ReadingOrder {
+    /// Lines of the block rearranged so each holds one operand, in operand order.
+    fn arrange(&self, rows: &[String]) -> Vec<String> {
+        match self {
+            ReadingOrder::Rows => rows.to_vec(),
+            ReadingOrder::ColumnsTopToBottom => text::transpose(rows),
+            ReadingOrder::ColumnsBottomToTop => text::rotate_clockwise(rows),
+            ReadingOrder::RightToLeft => text::rotate_counterclockwise(rows),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem<T> {
+    pub operands: Vec<T>,
+    pub op: String,
+    /// Columns of the worksheet the problem occupies.
+    pub column_span: Range<usize>,
+}
+
+/// Problems laid out side by side in fixed-width blocks, separated by columns of spaces, with a
+/// row of operators underneath.
+#[derive(Debug, Clone)]
+pub struct Worksheet {
+    blocks: Vec<Block>,
+}
+
+/// A problem's cells as written: one string per operand row, each as wide as the block.
+#[derive(Debug, Clone)]
+struct Block {
+    rows: Vec<String>,
+    op: String,
+    column_span: Range<usize>,
+}
+
+impl Worksheet {
+    /// Splits the worksheet into blocks on columns blank in every operand row, checking that every
+    /// block's operand rows hold one unbroken number each, all aligned to the same side, and that
+    /// its operator sits in its first column. An operator may be wider than its block; the next
+    /// block's operator then follows it after a space.
+    pub fn parse(input: &str) -> Result<Self, Diagnostic> {
+        if let Some(offset) = input.find(|c: char| !c.is_ascii()) {
+            return Err(Diagnostic::at(input, offset, "unexpected non-ASCII character"));
+        }
+        let mut line_starts = Vec::new();
+        let mut lines = Vec::new();
+        let mut offset = 0;
+        for line in input.split_inclusive('\n') {
+            line_starts.push(offset);
+            lines.push(line.trim_end_matches(['\n', '\r']).as_bytes());
+            offset += line.len();
+        }
+        while lines.last().is_some_and(|line| line.iter().all(u8::is_ascii_whitespace)) {
+            lines.pop();
+        }
+        let Some((operators, operands)) = lines.split_last().filter(|(_, operands)| !operands.is_empty()) else {
+            return Err(Diagnostic::at(input, 0, "expected rows of operands followed by a row of operators"));
+        };
+        let op_row = operands.len();
+        let width = operands.iter().map(|line| line.len()).max().unwrap_or(0);
+        let cell = |row: usize, column: usize| lines[row].get(column).copied().unwrap_or(b' ');
+        let error = |row: usize, column: usize, cause: String| {
+            Diagnostic::at(input, line_starts[row] + column.min(lines[row].len()), cause)
+        };
+
+        let mut spans = Vec::new();
+        let mut start = None;
+        for column in 0..=width {
+            let blank = column == width || (0..op_row).all(|row| cell(row, column) == b' ');
+            match (start, blank) {
+                (None, false) => start = Some(column),
+                (Some(s), true) => {
+                    spans.push(s..column);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        let mut tokens = Vec::new();
+        let mut column = 0;
+        while column < operators.len() {
+            let start = column;
+            column += operators[start..].iter().take_while(|c| **c != b' ').count();
+            if column > start {
+                tokens.push(start..column);
+            }
+            column += 1;
+        }
+        let mut tokens = tokens.into_iter().peekable();
+
+        let mut blocks = Vec::with_capacity(spans.len());
+        let mut previous_op_end = None;
+        for (i, span) in spans.iter().cloned().enumerate() {
+            let next_start = spans.get(i + 1).map_or(usize::MAX, |next| next.start);
+            // An operator wider than its problem can run up to or past the start of the next one,
+            // which then takes the next operator along instead
+            let displaced = previous_op_end.is_some_and(|end| end >= span.start);
+            let token = match tokens.next_if(|token| token.start < next_start) {
+                Some(token) if token.start == span.start || (displaced && token.start > span.start) => token,
+                Some(token) => {
+                    return Err(error(op_row, token.start, "operator doesn't line up with the start of its problem".to_owned()));
+                }
+                None => return Err(error(op_row, span.start, "missing operator".to_owned())),
+            };
+            if let Some(extra) = tokens.peek().filter(|extra| extra.start < next_start) {
+                return Err(error(op_row, extra.start, "more than one operator under a problem".to_owned()));
+            }
+            previous_op_end = Some(token.end);
+            let op = String::from_utf8_lossy(&operators[token]).into_owned();
+
+            let mut rows = Vec::with_capacity(operands.len());
+            let mut alignment = None;
+            for row in 0..operands.len() {
+                let cells: Vec<u8> = span.clone().map(|c| cell(row, c)).collect();
+                if let Some(i) = cells.iter().position(|c| *c != b' ' && !c.is_ascii_digit()) {
+                    return Err(error(row, span.start + i, format!("unexpected character {:?}", cells[i] as char)));
+                }
+                let Some(first) = cells.iter().position(u8::is_ascii_digit) else {
+                    return Err(error(row, span.start, "missing operand".to_owned()));
+                };
+                let last = cells.iter().rposition(u8::is_ascii_digit).expect("a digit was found");
+                if let Some(gap) = (first..last).find(|&i| cells[i] == b' ') {
+                    return Err(error(row, span.start + gap, "misaligned digits: gap inside a number".to_owned()));
+                }
+                // Numbers narrower than the block must all hug the same side of it
+                match (first == 0, last == cells.len() - 1) {
+                    (true, true) => {}
+                    (false, false) => {
+                        return Err(error(row, span.start + first, "misaligned digits: number touches neither side of its problem".to_owned()));
+                    }
+                    (left, _) => {
+                        if alignment.is_some_and(|aligned_left| aligned_left != left) {
+                            return Err(error(row, span.start + first, "misaligned digits: numbers aligned both left and right".to_owned()));
+                        }
+                        alignment = Some(left);
+                    }
+                }
+                rows.push(String::from_utf8(cells).expect("cells are ASCII"));
+            }
+            blocks.push(Block { rows, op, column_span: span });
+        }
+        Ok(Worksheet { blocks })
+    }
+
+    /// Reads every problem's operands in the given order.
+    pub fn problems<T: Number>(&self, order: ReadingOrder) -> anyhow::Result<Vec<Problem<T>>> {
+        self.blocks
+            .iter()
+            .map(|block| {
+                let operands = order
+                    .arrange(&block.rows)
+                    .iter()
+                    .map(|line| line.replace(' ', "").parse().ok())
+                    .collect::<Option<_>>()
+                    .with_context(|| format!("Operand out of range in problem at columns {:?}", block.column_span))?;
+                Ok(Problem { operands, op: block.op.clone(), column_span: block.column_span.clone() })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n";
+
+    #[test]
+    fn splits_blocks_on_blank_columns() {
+        let problems = Worksheet::parse(EXAMPLE).unwrap().problems::<u64>(ReadingOrder::Rows).unwrap();
+        assert_eq!(problems.len(), 4);
+        assert_eq!(problems[0], Problem { operands: vec![123, 45, 6], op: "*".to_owned(), column_span: 0..3 });
+        assert_eq!(problems[3].column_span, 12..15);
+    }
+
+    fn operands(order: ReadingOrder) -> Vec<Vec<u64>> {
+        let problems = Worksheet::parse(EXAMPLE).unwrap().problems::<u64>(order).unwrap();
+        problems.into_iter().map(|problem| problem.operands).collect()
+    }
+
+    #[test]
+    fn reads_rows() {
+        assert_eq!(operands(ReadingOrder::Rows), vec![vec![123, 45, 6], vec![328, 64, 98], vec![51, 387, 215], vec![64, 23, 314]]);
+    }
+
+    #[test]
+    fn reads_columns_top_to_bottom() {
+        assert_eq!(operands(ReadingOrder::ColumnsTopToBottom), vec![vec![1, 24, 356], vec![369, 248, 8], vec![32, 581, 175], vec![623, 431, 4]]);
+    }
+
+    #[test]
+    fn reads_columns_bottom_to_top() {
+        assert_eq!(operands(ReadingOrder::ColumnsBottomToTop), vec![vec![1, 42, 653], vec![963, 842, 8], vec![23, 185, 571], vec![326, 134, 4]]);
+    }
+
+    #[test]
+    fn reads_right_to_left() {
+        assert_eq!(operands(ReadingOrder::RightToLeft), vec![vec![356, 24, 1], vec![8, 248, 369], vec![175, 581, 32], vec![4, 431, 623]]);
+    }
+
+    #[test]
+    fn handles_ragged_lines() {
+        let worksheet = Worksheet::parse("12 3\n4  56\n+  *").unwrap();
+        let problems = worksheet.problems::<u64>(ReadingOrder::Rows).unwrap();
+        assert_eq!(problems[0].operands, vec![12, 4]);
+        assert_eq!(problems[1].operands, vec![3, 56]);
+    }
+
+    fn evaluate<T: Number + 'static>(input: &str, evaluation: Evaluation) -> anyhow::Result<T> {
+        go(&Worksheet::parse(input).unwrap(), ReadingOrder::Rows, &Operators::standard(), evaluation)
+    }
+
+    #[test]
+    fn reports_overflow_or_widens() {
+        let input = "1 99999999999\n2 99999999999\n+ *";
+        let error = evaluate::<u64>(input, Evaluation::Separately).unwrap_err();
+        assert_eq!(error.to_string(), "Arithmetic overflow or undefined `*` in problem at columns 2..13");
+        let total: Integer = evaluate(input, Evaluation::Separately).unwrap();
+        assert_eq!(total.to_string(), "9999999999800000000004");
+        let input = "99999999999999999999\n1\n+";
+        assert!(evaluate::<u64>(input, Evaluation::Separately).is_err());
+        assert_eq!(evaluate::<Integer>(input, Evaluation::Separately).unwrap().to_string(), "100000000000000000000");
+    }
+
+    #[test]
+    fn folds_standard_operators_left_to_right() {
+        assert_eq!(evaluate::<i64>("20 7\n5  4\n3  5\n-  %", Evaluation::Separately).unwrap(), 12 + 3);
+        assert_eq!(evaluate::<i64>("100 500\n7   600\n2   9\n/   max", Evaluation::Separately).unwrap(), 7 + 600);
+        assert_eq!(evaluate::<u64>("8\n3\n5\nmin", Evaluation::Separately).unwrap(), 3);
+        assert_eq!(evaluate::<u64>("42\n+", Evaluation::Separately).unwrap(), 42);
+        assert!(evaluate::<u64>("5\n6\n-", Evaluation::Separately).is_err());
+        let error = evaluate::<Integer>("5\n0\n/", Evaluation::Separately).unwrap_err();
+        assert_eq!(error.to_string(), "Arithmetic overflow or undefined `/` in problem at columns 0..1");
+        let error = evaluate::<u64>("5 5\n5 5\n+ &", Evaluation::Separately).unwrap_err();
+        assert_eq!(error.to_string(), "Unknown operator `&` in problem at columns 2..3");
+    }
+
+    #[test]
+    fn lets_operators_run_past_their_problems() {
+        assert_eq!(evaluate::<u64>("5\n6\nmax", Evaluation::Separately).unwrap(), 6);
+        assert_eq!(evaluate::<u64>("5 7\n6 8\nmax min", Evaluation::Separately).unwrap(), 6 + 7);
+        assert_eq!(evaluate::<u64>("12 9\n3  8\nmin +", Evaluation::Separately).unwrap(), 3 + 17);
+        assert_eq!(error("5 7\n6 8\nmax\n"), (3, 3, "missing operator".to_owned()));
+        assert_eq!(error("5 7\n6 8\nmax - +\n"), (3, 7, "more than one operator under a problem".to_owned()));
+    }
+
+    #[test]
+    fn applies_precedence_across_the_row() {
+        let example: u64 = evaluate(EXAMPLE, Evaluation::Separately).unwrap();
+        assert_eq!(evaluate::<u64>(EXAMPLE, Evaluation::Precedence).unwrap(), example);
+        // min(100, 300 + 2 + 4) rather than min(100, 300) + (2 + 4).
+        let input = "100 2\n300 4\nmin +";
+        assert_eq!(evaluate::<u64>(input, Evaluation::Separately).unwrap(), 106);
+        assert_eq!(evaluate::<u64>(input, Evaluation::Precedence).unwrap(), 100);
+    }
+
+    #[test]
+    fn registers_custom_operators() {
+        let mut operators = Operators::<u64>::standard();
+        operators.register(Operator::new("^", 3, |a: &u64, b: &u64| a.checked_pow(u32::try_from(*b).ok()?)));
+        let worksheet = Worksheet::parse("2  1\n10 1\n^  +").unwrap();
+        assert_eq!(go(&worksheet, ReadingOrder::Rows, &operators, Evaluation::Separately).unwrap(), 1024 + 2);
+        let replaced = operators.register(Operator::new("+", 1, |a: &u64, b: &u64| a.checked_sub(b)));
+        assert_eq!(replaced.map(|operator| operator.symbol), Some("+".to_owned()));
+        assert_eq!(go(&worksheet, ReadingOrder::Rows, &operators, Evaluation::Separately).unwrap(), 1024);
+    }
+
+    fn error(input: &str) -> (usize, usize, String) {
+        let error = Worksheet::parse(input).unwrap_err();
+        (error.line, error.column, error.cause.unwrap())
+    }
+
+    #[test]
+    fn locates_misaligned_operators() {
+        assert_eq!(error("12 34\n 5 6\n +  *\n"), (3, 2, "operator doesn't line up with the start of its problem".to_owned()));
+        assert_eq!(error("123\n456\n+ *\n"), (3, 3, "more than one operator under a problem".to_owned()));
+    }
+
+    #[test]
+    fn locates_misaligned_digits() {
+        assert_eq!(error("1 3 45\n456 7\n*   +\n"), (1, 2, "misaligned digits: gap inside a number".to_owned()));
+        assert_eq!(error("12 45\n4x 78\n+  *\n"), (2, 2, "unexpected character 'x'".to_owned()));
+        assert_eq!(error("12 45\n   78\n+  *\n"), (2, 1, "missing operand".to_owned()));
+        assert_eq!(error("123\n4\n  5\n+\n"), (3, 3, "misaligned digits: numbers aligned both left and right".to_owned()));
+        assert_eq!(error("123\n 4\n+\n"), (2, 2, "misaligned digits: number touches neither side of its problem".to_owned()));
+    }
+
+    #[test]
+    fn reads_right_aligned_blocks() {
+        let worksheet = Worksheet::parse("123\n  4\n 56\n*").unwrap();
+        assert_eq!(worksheet.problems::<u64>(ReadingOrder::Rows).unwrap()[0].operands, vec![123, 4, 56]);
+        assert_eq!(worksheet.problems::<u64>(ReadingOrder::ColumnsTopToBottom).unwrap()[0].operands, vec![1, 25, 346]);
+        let worksheet = Worksheet::parse("12\n 3\n45\n+").unwrap();
+        assert_eq!(worksheet.problems::<u64>(ReadingOrder::RightToLeft).unwrap()[0].operands, vec![235, 14]);
+        assert_eq!(error("12\n"), (1, 1, "expected rows of operands followed by a row of operators".to_owned()));
+    }
+}
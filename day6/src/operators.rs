@@ -0,0 +1,144 @@
+use std::cmp;
+use std::collections::HashMap;
+use sdk::anyhow::{self, Context};
+use sdk::num::{add_to, Number};
+use crate::Problem;
+
+type Apply<T> = Box<dyn Fn(&T, &T) -> Option<T>>;
+
+/// A binary operator a problem can use. A problem folds its operands left to right, so
+/// `a - b - c` is `(a - b) - c`.
+pub struct Operator<T> {
+    pub symbol: String,
+    /// Higher binds tighter. Only used by [Evaluation::Precedence].
+    pub precedence: u8,
+    /// `None` when the result overflows or isn't defined, like dividing by zero.
+    apply: Apply<T>,
+}
+
+impl<T: Number> Operator<T> {
+    pub fn new(symbol: impl Into<String>, precedence: u8, apply: impl Fn(&T, &T) -> Option<T> + 'static) -> Self {
+        Operator { symbol: symbol.into(), precedence, apply: Box::new(apply) }
+    }
+
+    pub fn apply(&self, a: &T, b: &T, context: impl FnOnce() -> String) -> anyhow::Result<T> {
+        (self.apply)(a, b).with_context(|| format!("Arithmetic overflow or undefined `{}` in {}", self.symbol, context()))
+    }
+
+    /// Folds operands left to right. A problem with a single operand is just that operand.
+    pub fn fold(&self, operands: &[T], context: impl Fn() -> String) -> anyhow::Result<T> {
+        let (first, rest) = operands.split_first().with_context(|| format!("No operands in {}", context()))?;
+        rest.iter().try_fold(first.clone(), |acc, operand| self.apply(&acc, operand, &context))
+    }
+}
+
+/// How the problems in a worksheet combine into one answer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Evaluation {
+    /// Each problem is folded on its own, then the answers are added up.
+    Separately,
+    /// The worksheet reads as one expression: each problem's operands joined by its operator, and
+    /// problems joined by `+`. Tighter-binding operators go first, and ties go left to right. With
+    /// only `+` and `*` this matches [Evaluation::Separately], but operators binding looser than
+    /// `+`, like `max`, reach across problems.
+    Precedence,
+}
+
+/// Operators by symbol.
+pub struct Operators<T> {
+    by_symbol: HashMap<String, Operator<T>>,
+}
+
+impl<T: Number + 'static> Operators<T> {
+    /// `+`, `-`, `*`, `/` (rounding toward zero), `%`, `max` and `min`. `max` and `min` bind
+    /// loosest, and `*`, `/` and `%` tightest.
+    pub fn standard() -> Self {
+        let mut operators = Operators { by_symbol: HashMap::new() };
+        operators.register(Operator::new("+", 1, T::checked_add));
+        operators.register(Operator::new("-", 1, T::checked_sub));
+        operators.register(Operator::new("*", 2, T::checked_mul));
+        operators.register(Operator::new("/", 2, T::checked_div));
+        operators.register(Operator::new("%", 2, T::checked_rem));
+        operators.register(Operator::new("max", 0, |a: &T, b: &T| Some(cmp::max(a, b).clone())));
+        operators.register(Operator::new("min", 0, |a: &T, b: &T| Some(cmp::min(a, b).clone())));
+        operators
+    }
+}
+
+impl<T: Number> Operators<T> {
+    /// Adds an operator, replacing any with the same symbol.
+    pub fn register(&mut self, operator: Operator<T>) -> Option<Operator<T>> {
+        self.by_symbol.insert(operator.symbol.clone(), operator)
+    }
+
+    pub fn get(&self, symbol: &str) -> Option<&Operator<T>> {
+        self.by_symbol.get(symbol)
+    }
+
+    fn operator(&self, problem: &Problem<T>) -> anyhow::Result<&Operator<T>> {
+        self.get(&problem.op)
+            .with_context(|| format!("Unknown operator `{}` in problem at columns {:?}", problem.op, problem.column_span))
+    }
+
+    pub fn evaluate(&self, problems: &[Problem<T>], evaluation: Evaluation) -> anyhow::Result<T> {
+        match evaluation {
+            Evaluation::Separately => {
+                let mut total = T::zero();
+                for problem in problems {
+                    let value = self.operator(problem)?.fold(&problem.operands, || format!("problem at columns {:?}", problem.column_span))?;
+                    add_to(&mut total, &value, || "worksheet total".to_owned())?;
+                }
+                Ok(total)
+            }
+            Evaluation::Precedence => {
+                let plus = self.get("+").context("Joining problems needs a `+` operator")?;
+                let mut terms = Vec::new();
+                for (i, problem) in problems.iter().enumerate() {
+                    let operator = self.operator(problem)?;
+                    for (j, operand) in problem.operands.iter().enumerate() {
+                        let joiner = match (i, j) {
+                            (0, 0) => None,
+                            (_, 0) => Some(plus),
+                            _ => Some(operator),
+                        };
+                        terms.push((joiner, operand));
+                    }
+                }
+                evaluate_infix(&terms)
+            }
+        }
+    }
+}
+
+impl<T: Number + 'static> Default for Operators<T> {
+    fn default() -> Self {
+        Operators::standard()
+    }
+}
+
+/// Evaluates `v0 op1 v1 op2 v2 ...`, given as `(None, v0), (Some(op1), v1), ...`, by operator
+/// precedence with two stacks.
+fn evaluate_infix<T: Number>(terms: &[(Option<&Operator<T>>, &T)]) -> anyhow::Result<T> {
+    let context = || "worksheet expression".to_owned();
+    let mut values: Vec<T> = Vec::new();
+    let mut pending: Vec<&Operator<T>> = Vec::new();
+    let reduce = |values: &mut Vec<T>, operator: &Operator<T>| -> anyhow::Result<()> {
+        let b = values.pop().expect("an operator has two operands");
+        let a = values.pop().expect("an operator has two operands");
+        values.push(operator.apply(&a, &b, context)?);
+        Ok(())
+    };
+    for &(operator, value) in terms {
+        if let Some(operator) = operator {
+            while let Some(top) = pending.pop_if(|top| top.precedence >= operator.precedence) {
+                reduce(&mut values, top)?;
+            }
+            pending.push(operator);
+        }
+        values.push(value.clone());
+    }
+    while let Some(top) = pending.pop() {
+        reduce(&mut values, top)?;
+    }
+    values.pop().context("No problems in worksheet")
+}
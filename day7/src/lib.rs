@@ -0,0 +1,363 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use sdk::*;
+use sdk::anyhow::bail;
+use sdk::frames::FrameRecorder;
+use sdk::grid::{char_grid, Grid};
+use sdk::num::{add_to, Integer, Number, Overflow};
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    type Input = Manifold;
+    type Output1 = u64;
+    type Output2 = Integer;
+
+    fn parse(input: &str) -> anyhow::Result<Manifold> {
+        Ok(parse_manifold.parse_all(input)?)
+    }
+
+    fn part1(manifold: &Manifold) -> anyhow::Result<u64> {
+        Ok(go(manifold, &mut FrameRecorder::new("day7-part1"))?.split_count)
+    }
+
+    fn part2(manifold: &Manifold) -> anyhow::Result<Integer> {
+        go(manifold, &mut FrameRecorder::new("day7-part2"))?.path_count(manifold)
+    }
+}
+
+answer_tests!(Day7);
+
+/// A beam in a cell, heading in a direction. The cell's optic hasn't acted on it yet.
+type Beam = ((usize, usize), Direction);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+}
+
+/// Every beam state reached from the emitters.
+struct Trace {
+    beams: HashSet<Beam>,
+    /// Splitters that split at least one beam.
+    split_count: u64,
+}
+
+/// Follows beams out of every emitter a wave at a time, recording a frame per wave. A beam state
+/// that's already been reached isn't followed again, so beams caught in a loop still stop.
+fn go(manifold: &Manifold, frames: &mut FrameRecorder) -> anyhow::Result<Trace> {
+    let mut beams: HashSet<Beam> = emitters(manifold).collect();
+    let mut wave: Vec<Beam> = beams.iter().copied().collect();
+    let mut splitters = HashSet::new();
+    while !wave.is_empty() {
+        debug!("Wave of {} beams", wave.len());
+        let mut next = Vec::new();
+        for beam in wave {
+            if manifold[beam.0].is_some_and(|optic| optic.splits(beam.1)) {
+                splitters.insert(beam.0);
+            }
+            for beam in deflect(manifold, beam).into_iter().flatten() {
+                if beams.insert(beam) {
+                    next.push(beam);
+                }
+            }
+        }
+        wave = next;
+        frames.record(|| print_beams(manifold, &beams));
+    }
+    frames.finish()?;
+
+    debug!("Beam map: \n{}", print_beams(manifold, &beams));
+
+    Ok(Trace { beams, split_count: splitters.len() as u64 })
+}
+
+impl Trace {
+    /// Counts the distinct routes a beam can take from an emitter until it leaves the manifold.
+    /// Beams are counted in topological order, so any beams never reached that way are in or after
+    /// a loop. That's only an error if one of them leaves the manifold, since a loop that never
+    /// gets out adds no paths.
+    fn path_count<T: Number>(&self, manifold: &Manifold) -> anyhow::Result<T> {
+        let mut predecessors: HashMap<Beam, Vec<Beam>> = HashMap::new();
+        for &beam in &self.beams {
+            for next in deflect(manifold, beam).into_iter().flatten() {
+                predecessors.entry(next).or_default().push(beam);
+            }
+        }
+        let mut remaining: HashMap<Beam, usize> = predecessors.iter().map(|(beam, from)| (*beam, from.len())).collect();
+        let mut paths: HashMap<Beam, T> = HashMap::new();
+        let mut ready = VecDeque::new();
+        for beam in emitters(manifold) {
+            add_paths(&mut paths, beam, &T::one())?;
+            if !remaining.contains_key(&beam) {
+                ready.push_back(beam);
+            }
+        }
+        let mut total = T::zero();
+        let mut counted = HashSet::new();
+        while let Some(beam) = ready.pop_front() {
+            counted.insert(beam);
+            let count = paths.get(&beam).cloned().expect("Missing path");
+            for next in deflect(manifold, beam) {
+                let Some(next) = next else {
+                    add_to(&mut total, &count, || "total path count".to_owned())?;
+                    continue;
+                };
+                add_paths(&mut paths, next, &count)?;
+                let left = remaining.get_mut(&next).expect("counted above");
+                *left -= 1;
+                if *left == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+
+        // Every uncounted beam has an uncounted predecessor, so walking back through them must
+        // come round to a beam twice, and that beam is on a loop.
+        let escaping = self
+            .beams
+            .iter()
+            .filter(|beam| !counted.contains(beam) && deflect(manifold, **beam).contains(&None))
+            .min()
+            .copied();
+        if let Some(mut beam) = escaping {
+            let mut seen = HashSet::new();
+            while seen.insert(beam) {
+                beam = predecessors[&beam].iter().filter(|from| !counted.contains(from)).min().copied().expect("an uncounted predecessor");
+            }
+            let ((x, y), direction) = beam;
+            bail!("Beam loops through ({x}, {y}) heading {direction:?}, so there are infinitely many paths");
+        }
+        Ok(total)
+    }
+}
+
+fn add_paths<T: Number>(paths: &mut HashMap<Beam, T>, ((x, y), direction): Beam, count: &T) -> Result<(), Overflow> {
+    add_to(paths.entry(((x, y), direction)).or_insert_with(T::zero), count, || format!("path count at ({x}, {y})"))
+}
+
+fn emitters(manifold: &Manifold) -> impl Iterator<Item = Beam> + '_ {
+    manifold.positions().filter(|&position| matches!(manifold[position], Some(Optic::Emitter))).map(|position| (position, Direction::Down))
+}
+
+/// The cell next to `(x, y)` in `direction`, or `None` off the edge.
+fn step(manifold: &Manifold, (x, y): (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+    match direction {
+        Direction::Up => Some((x, y.checked_sub(1)?)),
+        Direction::Down => (y + 1 < manifold.height()).then_some((x, y + 1)),
+        Direction::Left => Some((x.checked_sub(1)?, y)),
+        Direction::Right => (x + 1 < manifold.width()).then_some((x + 1, y)),
+    }
+}
+
+/// Beams coming out of a beam's cell, where `None` is a beam leaving the manifold.
+fn deflect(manifold: &Manifold, (position, direction): Beam) -> Vec<Option<Beam>> {
+    let onward = |direction| step(manifold, position, direction).map(|next| (next, direction));
+    match (manifold[position], direction) {
+        (None | Some(Optic::Emitter), _) => vec![onward(direction)],
+        (Some(Optic::Absorber), _) => vec![],
+        // A falling beam steps into the cells either side and carries on falling from there. A
+        // side off the edge of the manifold is lost rather than leaving it.
+        (Some(Optic::Splitter), Direction::Down) => [Direction::Left, Direction::Right]
+            .into_iter()
+            .filter_map(|side| step(manifold, position, side))
+            .map(|next| Some((next, Direction::Down)))
+            .collect(),
+        (Some(Optic::ForwardMirror), _) => vec![onward(match direction {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Down,
+        })],
+        (Some(Optic::BackMirror), _) => vec![onward(match direction {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Down,
+        })],
+        (Some(Optic::VerticalSplitter), _) if !direction.is_vertical() => vec![onward(Direction::Up), onward(Direction::Down)],
+        (Some(Optic::HorizontalSplitter), _) if direction.is_vertical() => vec![onward(Direction::Left), onward(Direction::Right)],
+        (Some(Optic::Splitter | Optic::VerticalSplitter | Optic::HorizontalSplitter), _) => vec![onward(direction)],
+    }
+}
+
+pub type Manifold = Grid<Option<Optic>>;
+
+fn parse_manifold(input: &mut &str) -> winnow::Result<Manifold> {
+    char_grid(|c| match c {
+        '.' => Ok(None),
+        other => Optic::from_symbol(other).map(Some).ok_or_else(|| TextError(format!("Unexpected character {other:?}"))),
+    })
+    .parse_next(input)
+}
+
+/// Optics as parsed. Empty cells a beam crossed show `│` or `─` for the way it went, or `┼` for
+/// both, so beams never look like splitters.
+fn print_beams(manifold: &Manifold, beams: &HashSet<Beam>) -> String {
+    let mut crossings: HashMap<(usize, usize), (bool, bool)> = HashMap::new();
+    for &(position, direction) in beams {
+        let (vertical, horizontal) = crossings.entry(position).or_default();
+        if direction.is_vertical() {
+            *vertical = true;
+        } else {
+            *horizontal = true;
+        }
+    }
+    manifold.render(|position, optic| match (optic, crossings.get(&position)) {
+        (Some(optic), _) => optic.symbol(),
+        (None, Some((true, true))) => '┼',
+        (None, Some((true, false))) => '│',
+        (None, Some((false, true))) => '─',
+        (None, _) => '.',
+    })
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Optic {
+    /// `S`: sends a beam down.
+    Emitter,
+    /// `^`: splits a falling beam into falling beams on either side. Other beams pass.
+    Splitter,
+    /// `/`
+    ForwardMirror,
+    /// `\`
+    BackMirror,
+    /// `|`: splits horizontal beams up and down. Vertical beams pass.
+    VerticalSplitter,
+    /// `-`: splits vertical beams left and right. Horizontal beams pass.
+    HorizontalSplitter,
+    /// `#`: stops any beam.
+    Absorber,
+}
+
+impl Optic {
+    const ALL: [Optic; 7] = [
+        Optic::Emitter,
+        Optic::Splitter,
+        Optic::ForwardMirror,
+        Optic::BackMirror,
+        Optic::VerticalSplitter,
+        Optic::HorizontalSplitter,
+        Optic::Absorber,
+    ];
+
+    fn symbol(self) -> char {
+        match self {
+            Optic::Emitter => 'S',
+            Optic::Splitter => '^',
+            Optic::ForwardMirror => '/',
+            Optic::BackMirror => '\\',
+            Optic::VerticalSplitter => '|',
+            Optic::HorizontalSplitter => '-',
+            Optic::Absorber => '#',
+        }
+    }
+
+    /// Whether a beam heading in `direction` comes out as two.
+    fn splits(self, direction: Direction) -> bool {
+        match self {
+            Optic::Splitter => direction == Direction::Down,
+            Optic::VerticalSplitter => !direction.is_vertical(),
+            Optic::HorizontalSplitter => direction.is_vertical(),
+            _ => false,
+        }
+    }
+
+    fn from_symbol(symbol: char) -> Option<Optic> {
+        Optic::ALL.into_iter().find(|optic| optic.symbol() == symbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A funnel of splitters where every row doubles the number of paths.
+    fn doubling_manifold(rows: usize) -> Manifold {
+        let width = 2 * rows + 3;
+        let mut lines = vec![format!("{:.^width$}", "S")];
+        for row in 0..rows {
+            lines.push(".".repeat(width));
+            let splitters = vec!["^"; row + 1].join(".");
+            lines.push(format!("{splitters:.^width$}"));
+        }
+        Day7::parse(&lines.join("\n")).unwrap()
+    }
+
+    #[test]
+    fn reports_overflowing_cell() {
+        let manifold = doubling_manifold(70);
+        let beams = trace(&manifold);
+        let error = beams.path_count::<u64>(&manifold).unwrap_err();
+        assert!(error.to_string().starts_with("Arithmetic overflow in path count at ("), "{error}");
+        let paths: Integer = beams.path_count(&manifold).unwrap();
+        let small_manifold = doubling_manifold(10);
+        let small: u64 = trace(&small_manifold).path_count(&small_manifold).unwrap();
+        assert_eq!(small, 2_u64.pow(10));
+        assert_eq!(paths.to_string(), "1180591620717411303424");
+    }
+
+    fn trace(manifold: &Manifold) -> Trace {
+        go(manifold, &mut FrameRecorder::with_output("test", None)).unwrap()
+    }
+
+    fn energized(manifold: &Manifold, trace: &Trace) -> usize {
+        manifold.positions().filter(|position| trace.beams.iter().any(|(at, _)| at == position)).count()
+    }
+
+    #[test]
+    fn reflects_and_absorbs() {
+        let manifold = Day7::parse("S.#\n\\./\n...").unwrap();
+        let trace = trace(&manifold);
+        assert_eq!(energized(&manifold, &trace), 5);
+        assert_eq!(trace.split_count, 0);
+        assert_eq!(trace.path_count::<u64>(&manifold).unwrap(), 0);
+        assert_eq!(print_beams(&manifold, &trace.beams), "S.#\n\\─/\n...\n");
+    }
+
+    #[test]
+    fn splits_in_every_direction() {
+        let manifold = Day7::parse(".S.\n|-.\n...").unwrap();
+        let trace = trace(&manifold);
+        assert_eq!(trace.split_count, 2);
+        assert_eq!(print_beams(&manifold, &trace.beams), "│S.\n|-─\n│..\n");
+        // Up and down off the left column, and right off the middle row.
+        assert_eq!(trace.path_count::<u64>(&manifold).unwrap(), 3);
+    }
+
+    #[test]
+    fn stops_beams_caught_in_loops() {
+        let manifold = Day7::parse(".S...\n.-..\\\n.....\n.\\../").unwrap();
+        let trace = trace(&manifold);
+        assert_eq!(trace.split_count, 1);
+        assert_eq!(energized(&manifold, &trace), 12);
+        let error = trace.path_count::<u64>(&manifold).unwrap_err();
+        assert_eq!(error.to_string(), "Beam loops through (1, 1) heading Up, so there are infinitely many paths");
+    }
+
+    #[test]
+    fn counts_paths_around_loops_that_never_get_out() {
+        let manifold = Day7::parse(".S...\n#-..\\\n.....\n.\\../").unwrap();
+        let trace = trace(&manifold);
+        assert_eq!(energized(&manifold, &trace), 12);
+        assert_eq!(trace.path_count::<u64>(&manifold).unwrap(), 0);
+    }
+
+    #[test]
+    fn drops_beams_split_off_the_edge() {
+        let manifold = Day7::parse(".S.\n.^.\n^.^").unwrap();
+        let trace = trace(&manifold);
+        assert_eq!(trace.split_count, 3);
+        assert_eq!(trace.path_count::<u64>(&manifold).unwrap(), 2);
+        assert_eq!(print_beams(&manifold, &trace.beams), ".S.\n│^│\n^│^\n");
+    }
+}
@@ -0,0 +1,118 @@
+use sdk::*;
+use sdk::anyhow::{bail, Context};
+use sdk::dsu::DisjointSet;
+use sdk::spatial::{KdTree, Point3};
+use sdk::winnow::ascii::dec_int;
+use sdk::winnow::combinator::separated;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Input = Playground;
+    type Output1 = usize;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> anyhow::Result<Playground> {
+        Day8::parse_for(input, InputKind::Real)
+    }
+
+    fn parse_for(input: &str, kind: InputKind) -> anyhow::Result<Playground> {
+        let connections = match kind {
+            InputKind::Example => EXAMPLE_CONNECTIONS,
+            InputKind::Real => REAL_CONNECTIONS,
+        };
+        Ok(Playground {
+            boxes: parse_boxes.parse_all(input)?,
+            connections,
+            largest_circuits: LARGEST_CIRCUITS,
+        })
+    }
+
+    fn part1(playground: &Playground) -> anyhow::Result<usize> {
+        go(&playground.boxes, playground.connections, playground.largest_circuits)
+    }
+
+    fn part2(playground: &Playground) -> anyhow::Result<i64> {
+        let Some((a, b)) = connect_all(&playground.boxes)? else {
+            bail!("The boxes are already on one circuit, so no connection completes it");
+        };
+        debug!("final connection: {a} - {b}");
+        a.x.checked_mul(b.x).with_context(|| format!("Product of the x coordinates of {a} and {b} overflowed"))
+    }
+}
+
+answer_tests!(Day8);
+
+const EXAMPLE_CONNECTIONS: usize = 10;
+const REAL_CONNECTIONS: usize = 1000;
+const LARGEST_CIRCUITS: usize = 3;
+
+pub struct Playground {
+    boxes: Vec<Point3>,
+    /// How many of the closest pairs to connect for part 1.
+    connections: usize,
+    /// How many of the largest circuits to multiply together for part 1.
+    largest_circuits: usize,
+}
+
+/// Connects the `connections` closest pairs of boxes, whether or not they're already on the same
+/// circuit, and multiplies together the sizes of the `largest_circuits` largest circuits.
+fn go(boxes: &[Point3], connections: usize, largest_circuits: usize) -> anyhow::Result<usize> {
+    let mut circuits = DisjointSet::new(boxes.len());
+    for (a, b, _) in KdTree::new(boxes).closest_pairs().take(connections) {
+        circuits.union(a, b);
+    }
+    let circuit_sizes = circuits.sorted_sizes();
+    debug!("{} circuits, largest: {:?}", circuit_sizes.len(), &circuit_sizes[..circuit_sizes.len().min(largest_circuits)]);
+    if circuit_sizes.len() < largest_circuits {
+        bail!("Only {} circuits, wanted the largest {largest_circuits}", circuit_sizes.len());
+    }
+    let result = circuit_sizes.into_iter().take(largest_circuits).product();
+    Ok(result)
+}
+
+/// Connects the closest pairs until every box is on one circuit, returning the pair that joined
+/// the last two circuits, or `None` if there was never more than one circuit.
+fn connect_all(boxes: &[Point3]) -> anyhow::Result<Option<(Point3, Point3)>> {
+    let mut circuits = DisjointSet::new(boxes.len());
+    if circuits.component_count() <= 1 {
+        return Ok(None);
+    }
+    for (a, b, _) in KdTree::new(boxes).closest_pairs() {
+        if circuits.union(a, b) && circuits.component_count() == 1 {
+            return Ok(Some((boxes[a], boxes[b])));
+        }
+    }
+    bail!("{} boxes never form a single circuit", boxes.len())
+}
+
+fn parse_jbox(input: &mut &str) -> winnow::Result<Point3> {
+    separated(3, dec_int::<_, i64, _>, ',')
+        .map(|n: Vec<i64>| Point3::new(n[0], n[1], n[2]))
+        .parse_next(input)
+}
+
+fn parse_boxes(input: &mut &str) -> winnow::Result<Vec<Point3>> {
+    separated(1.., parse_jbox, '\n').parse_next(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_overflowing_product() {
+        let playground = Day8::parse("4000000000,0,0\n4000000000,1,0").unwrap();
+        let error = Day8::part2(&playground).unwrap_err();
+        assert_eq!(error.to_string(), "Product of the x coordinates of 4000000000,0,0 and 4000000000,1,0 overflowed");
+    }
+
+    #[test]
+    fn a_single_box_is_already_one_circuit() {
+        assert_eq!(connect_all(&[Point3::new(1, 2, 3)]).unwrap(), None);
+        let playground = Day8::parse("1,2,3").unwrap();
+        let error = Day8::part2(&playground).unwrap_err();
+        assert_eq!(error.to_string(), "The boxes are already on one circuit, so no connection completes it");
+    }
+}
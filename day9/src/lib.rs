@@ -0,0 +1,26 @@
+use sdk::*;
+use sdk::anyhow::bail;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    type Input = String;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<String> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(_input: &String) -> anyhow::Result<u64> {
+        bail!("Day 9 part 1 not solved yet")
+    }
+
+    fn part2(_input: &String) -> anyhow::Result<u64> {
+        bail!("Day 9 part 2 not solved yet")
+    }
+}
+
+// No answers recorded yet.
+answer_tests!(Day9, []);
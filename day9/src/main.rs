@@ -1,7 +1,6 @@
+use day9::Day9;
 use sdk::*;
 
 fn main() -> anyhow::Result<()> {
-    init();
-    info!("Hello, world!");
-    Ok(())
+    solution::main::<Day9>()
 }
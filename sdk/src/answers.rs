@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::fs;
+use anyhow::{bail, Context};
+use crate::input::Inputs;
+use crate::solution::solve;
+use crate::{InputKind, Part, Solution};
+
+/// Known-good answers for a day, read from `<inputs>/day<N>/answers.txt`. Each non-empty line
+/// is `<example|real> <part> <answer>`; lines starting with `#` are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct Answers {
+    expected: HashMap<(InputKind, Part), String>,
+}
+
+impl Answers {
+    /// Loads a day's answers, or an empty set if it has no answers file.
+    pub fn load(inputs: &Inputs, day: u8) -> anyhow::Result<Self> {
+        let path = inputs.dir().join(format!("day{day}")).join("answers.txt");
+        if !path.is_file() {
+            return Ok(Answers::default());
+        }
+        let text = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        Answers::parse(&text).with_context(|| format!("Malformed answers file {}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        let mut expected = HashMap::new();
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+            let mut fields = line.split_whitespace();
+            let (Some(kind), Some(part), Some(answer), None) = (fields.next(), fields.next(), fields.next(), fields.next()) else {
+                bail!("Expected `<example|real> <part> <answer>`, got {line:?}");
+            };
+            expected.insert((kind.parse()?, part.parse()?), answer.to_owned());
+        }
+        Ok(Answers { expected })
+    }
+
+    pub fn expected(&self, kind: InputKind, part: Part) -> Option<&str> {
+        self.expected.get(&(kind, part)).map(String::as_str)
+    }
+
+    /// The input kinds and parts that have an answer, in no particular order.
+    pub fn entries(&self) -> impl Iterator<Item = (InputKind, Part)> + '_ {
+        self.expected.keys().copied()
+    }
+}
+
+/// Backs the tests generated by [answer_tests](crate::answer_tests): solves one part of one input
+/// and compares it to the registry. A missing answer or input fails the test, since the macro only
+/// generates tests for entries a day says it has.
+pub fn check<S: Solution>(kind: InputKind, part: Part) {
+    let inputs = Inputs::from_env();
+    let answers = Answers::load(&inputs, S::DAY).expect("failed to load answers");
+    let expected = answers
+        .expected(kind, part)
+        .unwrap_or_else(|| panic!("No {kind} answer recorded for day {} part {part}", S::DAY));
+    assert!(inputs.exists(S::DAY, kind), "No {kind} input for day {}", S::DAY);
+    let input = inputs.load(S::DAY, kind).expect("failed to load input");
+    let actual = solve::<S>(kind, &input, part).expect("solution failed");
+    assert_eq!(actual, expected, "day {} part {part} {kind} answer", S::DAY);
+}
+
+/// Backs the coverage test generated by [answer_tests](crate::answer_tests): fails if the answers
+/// file records an entry that isn't in `checked`, so a newly recorded answer can't go untested.
+pub fn check_covered<S: Solution>(checked: &[(InputKind, Part)]) {
+    let answers = Answers::load(&Inputs::from_env(), S::DAY).expect("failed to load answers");
+    let mut unchecked: Vec<String> = answers
+        .entries()
+        .filter(|entry| !checked.contains(entry))
+        .map(|(kind, part)| format!("{kind} part {part}"))
+        .collect();
+    unchecked.sort();
+    assert!(unchecked.is_empty(), "Day {} has answers for {} that answer_tests! doesn't check", S::DAY, unchecked.join(", "));
+}
+
+/// Generates a `#[test]` that checks a [Solution] against the day's answers file for each listed
+/// entry, out of `example_part1`, `example_part2`, `real_part1` and `real_part2`, plus one that
+/// fails when the file records an entry that isn't listed. With no list it checks all four.
+#[macro_export]
+macro_rules! answer_tests {
+    (@entry example_part1) => {
+        ($crate::InputKind::Example, $crate::Part::One)
+    };
+    (@entry example_part2) => {
+        ($crate::InputKind::Example, $crate::Part::Two)
+    };
+    (@entry real_part1) => {
+        ($crate::InputKind::Real, $crate::Part::One)
+    };
+    (@entry real_part2) => {
+        ($crate::InputKind::Real, $crate::Part::Two)
+    };
+    (@test $solution:ty, $name:ident) => {
+        #[test]
+        fn $name() {
+            let (kind, part) = $crate::answer_tests!(@entry $name);
+            $crate::answers::check::<$solution>(kind, part);
+        }
+    };
+    ($solution:ty) => {
+        $crate::answer_tests!($solution, [example_part1, example_part2, real_part1, real_part2]);
+    };
+    ($solution:ty, [$($test:ident),* $(,)?]) => {
+        #[cfg(test)]
+        mod answer_tests {
+            use super::*;
+
+            $($crate::answer_tests!(@test $solution, $test);)*
+
+            #[test]
+            fn every_recorded_answer_is_checked() {
+                $crate::answers::check_covered::<$solution>(&[$($crate::answer_tests!(@entry $test)),*]);
+            }
+        }
+    };
+}
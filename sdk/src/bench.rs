@@ -0,0 +1,111 @@
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use log::{warn, LevelFilter};
+use crate::{InputKind, Part, Solution};
+
+#[derive(Debug, Copy, Clone)]
+pub struct BenchConfig {
+    /// Untimed runs before measuring, to warm caches and the allocator.
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig { warmup: 3, iterations: 20 }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let total: Duration = samples.iter().sum();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            mean: total / samples.len() as u32,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub day: u8,
+    pub kind: InputKind,
+    pub parse: Stats,
+    /// Timings per requested part; `None` when the part returned an error, which is logged.
+    pub parts: Vec<(Part, Option<Stats>)>,
+}
+
+/// Times parsing and each requested part separately. Logging is turned down to warnings while
+/// measuring, so debug output doesn't dominate the timings.
+pub fn bench<S: Solution>(kind: InputKind, input: &str, parts: &[Part], config: BenchConfig) -> anyhow::Result<BenchReport> {
+    let iterations = config.iterations.max(1);
+    let level = log::max_level();
+    log::set_max_level(level.min(LevelFilter::Warn));
+    let result = (|| {
+        let parse = measure(config.warmup, iterations, || S::parse_for(input, kind).map(drop))?;
+        let parsed = S::parse_for(input, kind)?;
+        let parts = parts
+            .iter()
+            .map(|part| {
+                let stats = match part {
+                    Part::One => measure(config.warmup, iterations, || S::part1(&parsed).map(drop)),
+                    Part::Two => measure(config.warmup, iterations, || S::part2(&parsed).map(drop)),
+                };
+                let stats = stats.inspect_err(|error| warn!("Day {} part {part} {kind} failed: {error:#}", S::DAY));
+                (*part, stats.ok())
+            })
+            .collect();
+        Ok(BenchReport { day: S::DAY, kind, parse, parts })
+    })();
+    log::set_max_level(level);
+    result
+}
+
+fn measure(warmup: usize, iterations: usize, mut f: impl FnMut() -> anyhow::Result<()>) -> anyhow::Result<Stats> {
+    for _ in 0..warmup {
+        black_box(f())?;
+    }
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        black_box(f())?;
+        samples.push(start.elapsed());
+    }
+    Ok(Stats::from_samples(samples))
+}
+
+/// Renders reports as a table with one row per day, input and stage.
+pub fn format_table(reports: &[BenchReport]) -> String {
+    let mut table = String::new();
+    let _ = writeln!(table, "{:>3}  {:<7}  {:<6}  {:>12}  {:>12}  {:>12}", "day", "input", "stage", "min", "median", "mean");
+    for report in reports {
+        let stages = std::iter::once(("parse".to_owned(), Some(report.parse)))
+            .chain(report.parts.iter().map(|(part, stats)| (format!("part {part}"), *stats)));
+        for (stage, stats) in stages {
+            let _ = match stats {
+                Some(stats) => writeln!(
+                    table,
+                    "{:>3}  {:<7}  {:<6}  {:>12}  {:>12}  {:>12}",
+                    report.day,
+                    report.kind,
+                    stage,
+                    format!("{:.2?}", stats.min),
+                    format!("{:.2?}", stats.median),
+                    format!("{:.2?}", stats.mean),
+                ),
+                None => writeln!(table, "{:>3}  {:<7}  {:<6}  {:>12}  {:>12}  {:>12}", report.day, report.kind, stage, "-", "-", "-"),
+            };
+        }
+    }
+    table
+}
@@ -0,0 +1,167 @@
+use std::fmt::{Display, Formatter};
+use crate::TextError;
+
+/// A rotation of a [Dial]. Left counts down and right counts up; a negative distance turns the
+/// other way.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left(i64),
+    Right(i64),
+}
+
+impl Turn {
+    /// The signed number of clicks, positive meaning up. Widened so that `Left(i64::MIN)` can
+    /// still be negated.
+    pub fn clicks(&self) -> i128 {
+        match *self {
+            Turn::Left(n) => -i128::from(n),
+            Turn::Right(n) => i128::from(n),
+        }
+    }
+}
+
+impl Display for Turn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Turn::Left(n) => write!(f, "L{n}"),
+            Turn::Right(n) => write!(f, "R{n}"),
+        }
+    }
+}
+
+/// What happened during one [Dial::rotate].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Rotation {
+    pub turn: Turn,
+    pub from: u64,
+    pub to: u64,
+    /// Clicks during the rotation that landed on 0, including the last one.
+    pub crossings: u64,
+}
+
+impl Display for Rotation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} -> {}, crossed 0 {} times", self.turn, self.from, self.to, self.crossings)
+    }
+}
+
+/// A counter modulo `size`, turned a click at a time, that keeps track of how often it points at 0.
+#[derive(Debug, Clone)]
+pub struct Dial {
+    size: u64,
+    position: u64,
+    trace: Option<Vec<Rotation>>,
+}
+
+impl Dial {
+    /// A dial with positions `0..size`, pointing at `start` wrapped into range.
+    pub fn new(size: u64, start: i64) -> Result<Self, TextError> {
+        if size == 0 {
+            return Err(TextError("Dial size must be positive".to_owned()));
+        }
+        let position = (start as i128).rem_euclid(size as i128) as u64;
+        Ok(Dial { size, position, trace: None })
+    }
+
+    /// Records every rotation from now on, readable through [Dial::trace].
+    pub fn traced(mut self) -> Self {
+        self.trace.get_or_insert_with(Vec::new);
+        self
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Rotations so far, or an empty slice if tracing isn't on.
+    pub fn trace(&self) -> &[Rotation] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    pub fn rotate(&mut self, turn: Turn) -> Rotation {
+        let (size, from) = (self.size as i128, self.position as i128);
+        let clicks = turn.clicks();
+        let to = (from + clicks).rem_euclid(size);
+        // Measure from the last 0 behind us in the direction of travel; every full revolution past
+        // it is another click on 0
+        let behind = if clicks >= 0 { from } else { (size - from) % size };
+        let crossings = ((behind + clicks.abs()) / size) as u64;
+        let rotation = Rotation { turn, from: self.position, to: to as u64, crossings };
+        self.position = rotation.to;
+        if let Some(trace) = &mut self.trace {
+            trace.push(rotation);
+        }
+        rotation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Turns one click at a time, counting each landing on 0.
+    fn click_by_click(dial: &Dial, turn: Turn) -> (u64, u64) {
+        let (size, mut position) = (dial.size as i128, dial.position as i128);
+        let step = turn.clicks().signum();
+        let mut crossings = 0;
+        for _ in 0..turn.clicks().abs() {
+            position = (position + step).rem_euclid(size);
+            crossings += (position == 0) as u64;
+        }
+        (position as u64, crossings)
+    }
+
+    #[test]
+    fn wraps_both_ways() {
+        let mut dial = Dial::new(100, 50).unwrap();
+        assert_eq!(dial.rotate(Turn::Left(68)), Rotation { turn: Turn::Left(68), from: 50, to: 82, crossings: 1 });
+        assert_eq!(dial.rotate(Turn::Right(18)).crossings, 1);
+        assert_eq!(dial.position(), 0);
+        assert_eq!(dial.rotate(Turn::Left(5)).crossings, 0);
+        assert_eq!(dial.rotate(Turn::Right(1000)).crossings, 10);
+        assert_eq!(dial.rotate(Turn::Left(-10)).to, 5);
+        assert_eq!(Dial::new(7, -1).unwrap().position(), 6);
+        assert!(Dial::new(0, 0).is_err());
+    }
+
+    #[test]
+    fn turns_the_full_i64_range() {
+        let mut dial = Dial::new(100, 0).unwrap();
+        let rotation = dial.rotate(Turn::Left(i64::MIN));
+        assert_eq!(Turn::Left(i64::MIN).clicks(), 1 << 63);
+        assert_eq!((rotation.to, rotation.crossings), (8, (1 << 63) / 100));
+        let rotation = dial.rotate(Turn::Right(i64::MIN));
+        assert_eq!((rotation.to, rotation.crossings), (0, (1 << 63) / 100 + 1));
+    }
+
+    #[test]
+    fn matches_click_by_click() {
+        for size in [1, 2, 7, 100] {
+            for start in 0..size {
+                for clicks in -250..=250 {
+                    let turn = if clicks % 2 == 0 { Turn::Right(clicks) } else { Turn::Left(-clicks) };
+                    let mut dial = Dial::new(size as u64, start).unwrap();
+                    let expected = click_by_click(&dial, turn);
+                    let rotation = dial.rotate(turn);
+                    assert_eq!((rotation.to, rotation.crossings), expected, "{turn} from {start} on a dial of {size}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn traces_when_asked() {
+        let mut dial = Dial::new(10, 0).unwrap();
+        dial.rotate(Turn::Right(3));
+        assert!(dial.trace().is_empty());
+        let mut dial = dial.traced();
+        dial.rotate(Turn::Right(3));
+        dial.rotate(Turn::Left(16));
+        let trace: Vec<_> = dial.trace().iter().map(ToString::to_string).collect();
+        assert_eq!(trace, vec!["R3: 3 -> 6, crossed 0 0 times", "L16: 6 -> 0, crossed 0 2 times"]);
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Union-find over the indices `0..len`, with path compression and union by size.
+#[derive(Debug, Clone, Default)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    /// `len` singleton components.
+    pub fn new(len: usize) -> Self {
+        DisjointSet {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            components: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Adds a new singleton component and returns its index.
+    pub fn push(&mut self) -> usize {
+        let index = self.parent.len();
+        self.parent.push(index);
+        self.size.push(1);
+        self.components += 1;
+        index
+    }
+
+    /// The representative of the component containing `index`.
+    pub fn find(&mut self, index: usize) -> usize {
+        let mut root = index;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut current = index;
+        while self.parent[current] != root {
+            current = std::mem::replace(&mut self.parent[current], root);
+        }
+        root
+    }
+
+    /// Merges the components containing `a` and `b`. Returns false if they were already joined.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (large, small) = if self.size[a] >= self.size[b] { (a, b) } else { (b, a) };
+        self.parent[small] = large;
+        self.size[large] += self.size[small];
+        self.components -= 1;
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Number of elements in the component containing `index`.
+    pub fn component_size(&mut self, index: usize) -> usize {
+        let root = self.find(index);
+        self.size[root]
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// The size of every component, largest first.
+    pub fn sorted_sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<_> = (0..self.parent.len())
+            .filter(|&i| self.parent[i] == i)
+            .map(|root| self.size[root])
+            .collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+}
+
+/// A [DisjointSet] over arbitrary hashable values. Values are added the first time they're seen.
+#[derive(Debug, Clone)]
+pub struct KeyedDisjointSet<K> {
+    indices: HashMap<K, usize>,
+    set: DisjointSet,
+}
+
+impl<K> Default for KeyedDisjointSet<K> {
+    fn default() -> Self {
+        KeyedDisjointSet { indices: HashMap::new(), set: DisjointSet::default() }
+    }
+}
+
+impl<K: Hash + Eq> KeyedDisjointSet<K> {
+    pub fn new() -> Self {
+        KeyedDisjointSet::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    /// Adds `key` as a singleton if it isn't already present, and returns its index.
+    pub fn insert(&mut self, key: K) -> usize {
+        let set = &mut self.set;
+        *self.indices.entry(key).or_insert_with(|| set.push())
+    }
+
+    /// Merges the components containing `a` and `b`, adding either if needed. Returns false if they
+    /// were already joined.
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let (a, b) = (self.insert(a), self.insert(b));
+        self.set.union(a, b)
+    }
+
+    /// Whether both keys are present and in the same component.
+    pub fn same(&mut self, a: &K, b: &K) -> bool {
+        match (self.indices.get(a), self.indices.get(b)) {
+            (Some(&a), Some(&b)) => self.set.same(a, b),
+            _ => false,
+        }
+    }
+
+    /// Number of keys in the component containing `key`, or `None` if it was never added.
+    pub fn component_size(&mut self, key: &K) -> Option<usize> {
+        let index = *self.indices.get(key)?;
+        Some(self.set.component_size(index))
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.set.component_count()
+    }
+
+    /// The size of every component, largest first.
+    pub fn sorted_sizes(&self) -> Vec<usize> {
+        self.set.sorted_sizes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unions_by_index() {
+        let mut set = DisjointSet::new(6);
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(set.union(1, 3));
+        assert!(!set.union(0, 2));
+        assert!(set.same(0, 3));
+        assert!(!set.same(0, 4));
+        assert_eq!(set.component_size(2), 4);
+        assert_eq!(set.component_count(), 3);
+        assert_eq!(set.sorted_sizes(), vec![4, 1, 1]);
+        let added = set.push();
+        assert_eq!((added, set.len(), set.component_count()), (6, 7, 4));
+    }
+
+    #[test]
+    fn compresses_long_chains() {
+        let mut set = DisjointSet::new(1000);
+        for i in 1..1000 {
+            set.union(i - 1, i);
+        }
+        let root = set.find(0);
+        assert!((0..1000).all(|i| set.find(i) == root));
+        assert_eq!(set.sorted_sizes(), vec![1000]);
+    }
+
+    #[test]
+    fn unions_by_key() {
+        let mut set = KeyedDisjointSet::new();
+        set.union("a", "b");
+        set.union("c", "d");
+        set.insert("e");
+        assert!(set.same(&"a", &"b"));
+        assert!(!set.same(&"a", &"c"));
+        assert!(!set.same(&"a", &"z"));
+        set.union("b", "d");
+        assert_eq!(set.component_size(&"c"), Some(4));
+        assert_eq!(set.component_size(&"z"), None);
+        assert_eq!((set.len(), set.component_count()), (5, 2));
+        assert_eq!(set.sorted_sizes(), vec![4, 1]);
+    }
+}
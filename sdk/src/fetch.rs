@@ -0,0 +1,186 @@
+use std::env;
+use std::fs;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use anyhow::{bail, Context};
+use log::{debug, info};
+use ureq::Agent;
+use crate::input::Inputs;
+use crate::InputKind;
+
+/// Session cookie value copied from a logged-in browser, usually set in the git-ignored `.env`
+/// (copy `.env.example`).
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const USER_AGENT_VAR: &str = "AOC_USER_AGENT";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+pub const YEAR: u16 = 2025;
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_USER_AGENT: &str = "github.com/stearnsc/aoc2025";
+/// Minimum time between two requests made by the same [Client].
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Authenticated HTTP client for the puzzle site, with a built-in rate limit.
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    session: String,
+    min_interval: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>, user_agent: &str) -> Self {
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .user_agent(user_agent)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+        Client {
+            agent,
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+            session: session.into(),
+            min_interval: DEFAULT_MIN_INTERVAL,
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Builds a client from `AOC_SESSION`, `AOC_USER_AGENT` and `AOC_BASE_URL`. Only the session
+    /// is required.
+    pub fn from_env() -> anyhow::Result<Self> {
+        let session = env::var(SESSION_VAR)
+            .with_context(|| format!("{SESSION_VAR} must be set (e.g. in .env, copied from .env.example) to talk to the puzzle site"))?;
+        let base_url = env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned());
+        let user_agent = env::var(USER_AGENT_VAR).unwrap_or_else(|_| DEFAULT_USER_AGENT.to_owned());
+        Ok(Client::new(base_url, session, &user_agent))
+    }
+
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    pub(crate) fn url(&self, path: &str) -> String {
+        format!("{}/{YEAR}{path}", self.base_url)
+    }
+
+    pub(crate) fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Blocks until at least `min_interval` has passed since the previous request.
+    pub(crate) fn throttle(&self) {
+        let mut last_request = self.last_request.lock().expect("rate limit lock poisoned");
+        if let Some(last) = *last_request {
+            let elapsed = last.elapsed();
+            if elapsed < self.min_interval {
+                let wait = self.min_interval - elapsed;
+                debug!("Rate limiting, waiting {wait:?} before next request");
+                thread::sleep(wait);
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+
+    pub fn get(&self, path: &str) -> anyhow::Result<String> {
+        self.throttle();
+        let url = self.url(path);
+        debug!("GET {url}");
+        let response = self.agent
+            .get(&url)
+            .header("Cookie", self.cookie())
+            .call()
+            .with_context(|| format!("Request to {url} failed"))?;
+        read_response(&url, response)
+    }
+
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> anyhow::Result<String> {
+        self.throttle();
+        let url = self.url(path);
+        debug!("POST {url}");
+        let response = self.agent
+            .post(&url)
+            .header("Cookie", self.cookie())
+            .send_form(form.iter().copied())
+            .with_context(|| format!("Request to {url} failed"))?;
+        read_response(&url, response)
+    }
+}
+
+fn read_response(url: &str, mut response: ureq::http::Response<ureq::Body>) -> anyhow::Result<String> {
+    let status = response.status().as_u16();
+    let body = response.body_mut().read_to_string()?;
+    match status {
+        200..=299 => Ok(body),
+        400 => bail!("{url} returned 400, the session token is probably missing or expired"),
+        404 => bail!("{url} returned 404, the puzzle is probably not unlocked yet"),
+        other => bail!("{url} returned {other}: {}", body.trim()),
+    }
+}
+
+/// Returns the real input for a day, downloading it into the inputs directory the first time. A
+/// day that is already on disk is never requested again.
+pub fn fetch_input(client: &Client, inputs: &Inputs, day: u8) -> anyhow::Result<String> {
+    if inputs.exists(day, InputKind::Real) {
+        debug!("Day {day} input already cached at {}", inputs.path(day, InputKind::Real).display());
+        return inputs.load(day, InputKind::Real);
+    }
+    let input = client.get(&format!("/day/{day}/input"))?;
+    let path = inputs.path(day, InputKind::Real);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    fs::write(&path, &input).with_context(|| format!("Failed to write {}", path.display()))?;
+    info!("Downloaded day {day} input to {}", path.display());
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::{temp_dir, TestServer};
+
+    #[test]
+    fn downloads_and_caches_input() {
+        let server = TestServer::start(vec![(200, "1,2,3\n".to_owned())]);
+        let inputs = Inputs::new(temp_dir("fetch-cache"));
+        let client = Client::new(server.url(), "secret", "test-agent");
+
+        assert_eq!(fetch_input(&client, &inputs, 3).unwrap(), "1,2,3\n");
+        assert_eq!(inputs.load(3, InputKind::Real).unwrap(), "1,2,3\n");
+        // The server only has one response queued, so a second request would fail
+        assert_eq!(fetch_input(&client, &inputs, 3).unwrap(), "1,2,3\n");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        let request = requests[0].to_lowercase();
+        assert!(request.starts_with("get /2025/day/3/input "));
+        assert!(request.contains("cookie: session=secret"));
+        assert!(request.contains("user-agent: test-agent"));
+    }
+
+    #[test]
+    fn reports_locked_puzzles() {
+        let server = TestServer::start(vec![(404, "Not Found".to_owned())]);
+        let inputs = Inputs::new(temp_dir("fetch-locked"));
+        let client = Client::new(server.url(), "secret", "test-agent");
+
+        let error = fetch_input(&client, &inputs, 25).unwrap_err();
+        assert!(error.to_string().contains("not unlocked yet"));
+        assert!(!inputs.exists(25, InputKind::Real));
+    }
+
+    #[test]
+    fn rate_limits_consecutive_requests() {
+        let server = TestServer::start(vec![(200, "a".to_owned()), (200, "b".to_owned())]);
+        let client = Client::new(server.url(), "secret", "test-agent")
+            .with_min_interval(Duration::from_millis(200));
+
+        let start = Instant::now();
+        client.get("/day/1/input").unwrap();
+        client.get("/day/2/input").unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+}
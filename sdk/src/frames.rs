@@ -0,0 +1,153 @@
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::Duration;
+use anyhow::Context;
+
+/// Environment variable that turns frame recording on: `ansi` to animate in the terminal, or a
+/// directory to write numbered text frames into.
+pub const FRAMES_VAR: &str = "AOC_FRAMES";
+/// Environment variable with the delay between animated frames, in milliseconds.
+pub const FRAME_DELAY_VAR: &str = "AOC_FRAME_DELAY_MS";
+pub const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+
+/// Where recorded frames go once a simulation finishes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameOutput {
+    /// Redraw each frame in place on stdout, pausing between them.
+    Ansi { delay: Duration },
+    /// Write each frame to `<dir>/<name>-<n>.txt`.
+    Files { dir: PathBuf },
+}
+
+static OUTPUT: OnceLock<Option<FrameOutput>> = OnceLock::new();
+
+/// Sets where every [FrameRecorder] sends its frames, overriding `AOC_FRAMES`. Only the first call
+/// has any effect, and it must come before the first recorder is created.
+pub fn set_output(output: FrameOutput) {
+    let _ = OUTPUT.set(Some(output));
+}
+
+fn output() -> Option<&'static FrameOutput> {
+    OUTPUT.get_or_init(output_from_env).as_ref()
+}
+
+fn output_from_env() -> Option<FrameOutput> {
+    match env::var(FRAMES_VAR).ok()?.as_str() {
+        "" => None,
+        "ansi" => {
+            let delay = env::var(FRAME_DELAY_VAR)
+                .ok()
+                .and_then(|ms| ms.parse().ok())
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_FRAME_DELAY);
+            Some(FrameOutput::Ansi { delay })
+        }
+        dir => Some(FrameOutput::Files { dir: dir.into() }),
+    }
+}
+
+/// Captures a text snapshot per simulation step. When recording is off, frames are never rendered,
+/// so simulations can record unconditionally.
+#[derive(Debug, Clone)]
+pub struct FrameRecorder {
+    name: String,
+    output: Option<FrameOutput>,
+    frames: Vec<String>,
+}
+
+impl FrameRecorder {
+    /// A recorder using the configured output, if any. `name` prefixes written frame files.
+    pub fn new(name: impl Into<String>) -> Self {
+        FrameRecorder::with_output(name, output().cloned())
+    }
+
+    pub fn with_output(name: impl Into<String>, output: Option<FrameOutput>) -> Self {
+        FrameRecorder { name: name.into(), output, frames: Vec::new() }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.output.is_some()
+    }
+
+    /// Renders and keeps a frame, if recording.
+    pub fn record(&mut self, render: impl FnOnce() -> String) {
+        if self.is_enabled() {
+            self.frames.push(render());
+        }
+    }
+
+    pub fn frames(&self) -> &[String] {
+        &self.frames
+    }
+
+    /// Plays or writes the recorded frames.
+    pub fn finish(&self) -> anyhow::Result<()> {
+        match &self.output {
+            None => Ok(()),
+            Some(FrameOutput::Ansi { delay }) => self.animate(&mut std::io::stdout().lock(), *delay),
+            Some(FrameOutput::Files { dir }) => self.write_files(dir),
+        }
+    }
+
+    /// Draws each frame over the last: clear the screen, move to the top left, then draw.
+    pub fn animate(&self, out: &mut impl Write, delay: Duration) -> anyhow::Result<()> {
+        for (i, frame) in self.frames.iter().enumerate() {
+            write!(out, "\x1b[2J\x1b[H{frame}\n{} frame {}/{}\n", self.name, i + 1, self.frames.len())?;
+            out.flush()?;
+            if i + 1 < self.frames.len() {
+                thread::sleep(delay);
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes frames as `<dir>/<name>-0001.txt` and so on, creating `dir` if needed.
+    pub fn write_files(&self, dir: &Path) -> anyhow::Result<()> {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        for (i, frame) in self.frames.iter().enumerate() {
+            let path = dir.join(format!("{}-{:04}.txt", self.name, i + 1));
+            fs::write(&path, frame).with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::temp_dir;
+
+    #[test]
+    fn skips_rendering_when_disabled() {
+        let mut recorder = FrameRecorder::with_output("test", None);
+        recorder.record(|| unreachable!("rendered a frame while disabled"));
+        assert!(recorder.frames().is_empty());
+        recorder.finish().unwrap();
+    }
+
+    #[test]
+    fn writes_numbered_frames() {
+        let dir = temp_dir("frames");
+        let mut recorder = FrameRecorder::with_output("day0", Some(FrameOutput::Files { dir: dir.clone() }));
+        recorder.record(|| "#.\n.#".to_owned());
+        recorder.record(|| "..\n..".to_owned());
+        recorder.finish().unwrap();
+        assert_eq!(fs::read_to_string(dir.join("day0-0001.txt")).unwrap(), "#.\n.#");
+        assert_eq!(fs::read_to_string(dir.join("day0-0002.txt")).unwrap(), "..\n..");
+    }
+
+    #[test]
+    fn animates_with_redraws() {
+        let mut recorder = FrameRecorder::with_output("day0", Some(FrameOutput::Ansi { delay: Duration::ZERO }));
+        recorder.record(|| "a".to_owned());
+        recorder.record(|| "b".to_owned());
+        let mut out = Vec::new();
+        recorder.animate(&mut out, Duration::ZERO).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out, "\x1b[2J\x1b[Ha\nday0 frame 1/2\n\x1b[2J\x1b[Hb\nday0 frame 2/2\n");
+    }
+}
@@ -0,0 +1,337 @@
+use std::ops::{Index, IndexMut};
+use winnow::error::{ContextError, FromExternalError, StrContext};
+use crate::TextError;
+
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const OFFSETS_8: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+/// A rectangular grid stored row-major, addressed by `(x, y)` with the origin at the top left.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: Vec<Vec<T>>) -> Result<Self, TextError> {
+        let height = rows.len();
+        let width = rows.first().map(|row| row.len()).unwrap_or(0);
+        if let Some(y) = rows.iter().position(|row| row.len() != width) {
+            return Err(TextError(format!(
+                "Not all rows are equal length: row {} has {} cells, expected {width}",
+                y + 1,
+                rows[y].len()
+            )));
+        }
+        Ok(Grid {
+            cells: rows.into_iter().flatten().collect(),
+            width,
+            height,
+        })
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self where T: Clone {
+        Grid {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Parses one row per line, mapping each character to a cell. Fails on ragged rows or on a
+    /// character the mapping rejects.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Self, TextError> {
+        let rows = input
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(x, c)| {
+                        f(c).ok_or_else(|| {
+                            TextError(format!("Unexpected character {c:?} at line {}, column {}", y + 1, x + 1))
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Grid::new(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// Panics if `(x, y)` is out of bounds.
+    pub fn set(&mut self, x: usize, y: usize, value: T) {
+        self[(x, y)] = value;
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Orthogonally adjacent positions that are inside the grid.
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        self.offset_positions(x, y, &OFFSETS_4)
+    }
+
+    /// Orthogonally and diagonally adjacent positions that are inside the grid.
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        self.offset_positions(x, y, &OFFSETS_8)
+    }
+
+    fn offset_positions(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let nx = x.checked_add_signed(*dx)?;
+            let ny = y.checked_add_signed(*dy)?;
+            (nx < width && ny < height).then_some((nx, ny))
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        (0..self.height).map(move |y| &self[(x, y)])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Diagonals running down and to the right, starting from the bottom-left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = (self.width as isize, self.height as isize);
+        (0..(width + height - 1).max(0)).map(move |i| {
+            let (start_x, start_y) = if i < height { (0, height - 1 - i) } else { (i - height + 1, 0) };
+            (0..)
+                .map(move |step| (start_x + step, start_y + step))
+                .take_while(move |(x, y)| *x < width && *y < height)
+                .map(|(x, y)| &self[(x as usize, y as usize)])
+        })
+    }
+
+    /// Diagonals running down and to the left, starting from the top-left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = (self.width as isize, self.height as isize);
+        (0..(width + height - 1).max(0)).map(move |i| {
+            let (start_x, start_y) = if i < width { (i, 0) } else { (width - 1, i - width + 1) };
+            (0..)
+                .map(move |step| (start_x - step, start_y + step))
+                .take_while(move |(x, y)| *x >= 0 && *y < height)
+                .map(|(x, y)| &self[(x as usize, y as usize)])
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Swaps rows and columns, so `(x, y)` moves to `(y, x)`.
+    pub fn transpose(&self) -> Self where T: Clone {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    pub fn rotate_clockwise(&self) -> Self where T: Clone {
+        let height = self.height;
+        self.remap(self.height, self.width, move |x, y| (y, height - 1 - x))
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self where T: Clone {
+        let width = self.width;
+        self.remap(self.height, self.width, move |x, y| (width - 1 - y, x))
+    }
+
+    /// Builds a `width` x `height` grid where each new `(x, y)` is read from `source(x, y)`.
+    fn remap(&self, width: usize, height: usize, source: impl Fn(usize, usize) -> (usize, usize)) -> Self where T: Clone {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self[source(x, y)].clone())
+            .collect();
+        Grid { cells, width, height }
+    }
+
+    /// Renders one line per row, using `f` to pick each cell's character.
+    pub fn render(&self, mut f: impl FnMut((usize, usize), &T) -> char) -> String {
+        let mut output = String::with_capacity((self.width + 1) * self.height);
+        for ((x, y), cell) in self.iter() {
+            output.push(f((x, y), cell));
+            if x == self.width - 1 {
+                output.push('\n');
+            }
+        }
+        output
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width && y < self.height, "({x}, {y}) is outside a {}x{} grid", self.width, self.height);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width && y < self.height, "({x}, {y}) is outside a {}x{} grid", self.width, self.height);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// A winnow parser for a rectangular block of lines, mapping each character through `cell`. It
+/// stops before a blank line or the end of input. On failure the input is left at the offending
+/// character, so [ParseAll](crate::ParseAll) can report its exact line and column.
+pub fn char_grid<T, E>(mut cell: impl FnMut(char) -> Result<T, E>) -> impl FnMut(&mut &str) -> winnow::Result<Grid<T>>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    move |input: &mut &str| {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        loop {
+            let line = &input[..input.find('\n').unwrap_or(input.len())];
+            let mut row = Vec::with_capacity(line.len());
+            for (i, c) in line.char_indices() {
+                match cell(c) {
+                    Ok(value) => row.push(value),
+                    Err(e) => {
+                        *input = &input[i..];
+                        return Err(ContextError::from_external_error(input, e));
+                    }
+                }
+            }
+            if let Some(width) = rows.first().map(|first| first.len())
+                && row.len() != width
+            {
+                let column = line.char_indices().nth(width).map(|(i, _)| i).unwrap_or(line.len());
+                *input = &input[column..];
+                let error = TextError(format!("Row has {} cells, expected {width}", row.len()));
+                return Err(ContextError::from_external_error(input, error));
+            }
+            if row.is_empty() {
+                let mut error = ContextError::from_external_error(input, TextError("Expected a grid row".to_owned()));
+                error.push(StrContext::Label("grid"));
+                return Err(error);
+            }
+            rows.push(row);
+            *input = &input[line.len()..];
+            match input.strip_prefix('\n') {
+                Some(rest) if !rest.is_empty() && !rest.starts_with(['\n', '\r']) => *input = rest,
+                _ => break,
+            }
+        }
+        Ok(Grid::new(rows).expect("rows were checked to be rectangular"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid<u32> {
+        Grid::parse("123\n456", |c| c.to_digit(10)).unwrap()
+    }
+
+    fn collect<'a>(lines: impl Iterator<Item = impl Iterator<Item = &'a u32>>) -> Vec<Vec<u32>> {
+        lines.map(|line| line.copied().collect()).collect()
+    }
+
+    #[test]
+    fn parses_and_indexes() {
+        let grid = digits();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get(3, 0), None);
+        assert!(Grid::parse("12\n3", |c| c.to_digit(10)).is_err());
+        let error = Grid::parse("12\n3x", |c| c.to_digit(10)).unwrap_err();
+        assert!(error.0.contains("line 2, column 2"));
+    }
+
+    #[test]
+    fn char_grid_reports_location() {
+        use crate::ParseAll;
+        let digit = |c: char| c.to_digit(10).ok_or_else(|| TextError(format!("Unexpected character {c:?}")));
+
+        let grid = char_grid(digit).parse_all("12\n34\n").unwrap();
+        assert_eq!(grid.row(1), &[3, 4]);
+
+        let error = char_grid(digit).parse_all("123\n4x6").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert!(error.cause.unwrap().contains("'x'"));
+
+        let error = char_grid(digit).parse_all("123\n4567").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+        let error = char_grid(digit).parse_all("123\n45").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = digits();
+        let mut n4: Vec<_> = grid.neighbors4(0, 0).collect();
+        n4.sort();
+        assert_eq!(n4, vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbors8(1, 0).count(), 5);
+        assert_eq!(grid.neighbors8(1, 1).count(), 5);
+    }
+
+    #[test]
+    fn lines() {
+        let grid = digits();
+        assert_eq!(grid.rows().collect::<Vec<_>>(), vec![&[1, 2, 3][..], &[4, 5, 6][..]]);
+        assert_eq!(collect(grid.columns()), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+        assert_eq!(collect(grid.diagonals()), vec![vec![4], vec![1, 5], vec![2, 6], vec![3]]);
+        assert_eq!(collect(grid.anti_diagonals()), vec![vec![1], vec![2, 4], vec![3, 5], vec![6]]);
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = digits();
+        let render = |grid: &Grid<u32>| grid.render(|_, d| char::from_digit(*d, 10).unwrap());
+        assert_eq!(render(&grid.transpose()), "14\n25\n36\n");
+        assert_eq!(render(&grid.rotate_clockwise()), "41\n52\n63\n");
+        assert_eq!(render(&grid.rotate_counterclockwise()), "36\n25\n14\n");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+}
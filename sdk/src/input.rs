@@ -0,0 +1,71 @@
+use std::env;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use anyhow::{bail, Context};
+use crate::InputKind;
+
+/// Environment variable (usually set in `.env`) that overrides where puzzle inputs are read from.
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS_DIR";
+
+/// Resolves puzzle inputs at runtime from a directory laid out as `<dir>/day<N>/example.txt` and
+/// `<dir>/day<N>/input.txt`.
+#[derive(Debug, Clone)]
+pub struct Inputs {
+    dir: PathBuf,
+}
+
+impl Inputs {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Inputs { dir: dir.into() }
+    }
+
+    /// Uses `AOC_INPUTS_DIR` if set, otherwise the `inputs` directory at the workspace root.
+    pub fn from_env() -> Self {
+        match env::var_os(INPUTS_DIR_VAR) {
+            Some(dir) => Inputs::new(dir),
+            None => Inputs::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("../inputs")),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, day: u8, kind: InputKind) -> PathBuf {
+        let file = match kind {
+            InputKind::Example => "example.txt",
+            InputKind::Real => "input.txt",
+        };
+        self.dir.join(format!("day{day}")).join(file)
+    }
+
+    pub fn exists(&self, day: u8, kind: InputKind) -> bool {
+        self.path(day, kind).is_file()
+    }
+
+    /// Input kinds that have a file on disk for the given day.
+    pub fn available(&self, day: u8) -> Vec<InputKind> {
+        InputKind::ALL
+            .iter()
+            .copied()
+            .filter(|kind| self.exists(day, *kind))
+            .collect()
+    }
+
+    pub fn load(&self, day: u8, kind: InputKind) -> anyhow::Result<String> {
+        let path = self.path(day, kind);
+        if !path.is_file() {
+            bail!("Day {day} {kind} input not found at {}", path.display());
+        }
+        std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read day {day} {kind} input from {}", path.display()))
+    }
+}
+
+pub fn read_stdin() -> anyhow::Result<String> {
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .context("Failed to read input from stdin")?;
+    Ok(input)
+}
@@ -0,0 +1,245 @@
+use std::ops::RangeInclusive;
+
+/// Integer-like values that have a well-defined next and previous value.
+pub trait Discrete: Copy + Ord {
+    fn succ(self) -> Option<Self>;
+    fn pred(self) -> Option<Self>;
+    /// Number of values in `start..=end`. Saturates at `u128::MAX`, which only the full range of
+    /// `i128` or `u128` reaches.
+    fn count(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty),*) => {
+        $(
+            impl Discrete for $t {
+                fn succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn count(start: Self, end: Self) -> u128 {
+                    (end as i128).abs_diff(start as i128).saturating_add(1)
+                }
+            }
+        )*
+    };
+}
+
+impl_discrete!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize);
+
+impl Discrete for u128 {
+    fn succ(self) -> Option<Self> {
+        self.checked_add(1)
+    }
+
+    fn pred(self) -> Option<Self> {
+        self.checked_sub(1)
+    }
+
+    fn count(start: Self, end: Self) -> u128 {
+        (end - start).saturating_add(1)
+    }
+}
+
+/// A set of values stored as sorted, disjoint, non-adjacent inclusive intervals. Overlapping or
+/// touching intervals are merged on insert, and membership is a binary search.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { intervals: Vec::new() }
+    }
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of disjoint intervals, not the number of values covered.
+    pub fn interval_count(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals.iter().map(|&(start, end)| start..=end)
+    }
+
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = (*range.start(), *range.end());
+        if start > end {
+            return;
+        }
+        // First interval that ends at or after the value just before `start`, i.e. that overlaps or
+        // touches the new one
+        let first = self.intervals.partition_point(|&(_, e)| e.succ().is_some_and(|after| after < start));
+        // One past the last interval that starts at or before the value just after `end`
+        let last = match end.succ() {
+            Some(after) => self.intervals.partition_point(|&(s, _)| s <= after),
+            None => self.intervals.len(),
+        };
+        if first < last {
+            start = start.min(self.intervals[first].0);
+            end = end.max(self.intervals[last - 1].1);
+        }
+        self.intervals.splice(first..last, [(start, end)]);
+    }
+
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = (*range.start(), *range.end());
+        if start > end {
+            return;
+        }
+        let first = self.intervals.partition_point(|&(_, e)| e < start);
+        let last = self.intervals.partition_point(|&(s, _)| s <= end);
+        if first >= last {
+            return;
+        }
+        let mut kept = Vec::with_capacity(2);
+        let (first_start, _) = self.intervals[first];
+        let (_, last_end) = self.intervals[last - 1];
+        if first_start < start {
+            kept.push((first_start, start.pred().expect("start is above an existing value")));
+        }
+        if last_end > end {
+            kept.push((end.succ().expect("end is below an existing value"), last_end));
+        }
+        self.intervals.splice(first..last, kept);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.intervals.partition_point(|&(_, end)| end < value);
+        self.intervals.get(i).is_some_and(|&(start, _)| start <= value)
+    }
+
+    /// Total number of values covered by the set.
+    /// Number of values in the set, saturating like [Discrete::count].
+    pub fn covered_len(&self) -> u128 {
+        self.intervals.iter().fold(0, |len, &(start, end)| len.saturating_add(T::count(start, end)))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for range in other.iter() {
+            union.insert(range);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(&(a_start, a_end)), Some(&(b_start, b_end))) = (self.intervals.get(i), other.intervals.get(j)) {
+            let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+            if start <= end {
+                intervals.push((start, end));
+            }
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.iter() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    /// The uncovered ranges strictly between the first and last intervals.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals.windows(2).map(|pair| {
+            let start = pair[0].1.succ().expect("intervals are non-adjacent");
+            let end = pair[1].0.pred().expect("intervals are non-adjacent");
+            start..=end
+        })
+    }
+}
+
+impl<T: Discrete> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(set: &IntervalSet<u64>) -> Vec<RangeInclusive<u64>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn merges_on_insert() {
+        let set: IntervalSet<u64> = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+        assert_eq!(ranges(&set), vec![3..=5, 10..=20]);
+        let mut set = set;
+        set.insert(6..=9);
+        assert_eq!(ranges(&set), vec![3..=20]);
+        set.insert(u64::MAX - 1..=u64::MAX);
+        assert_eq!(set.interval_count(), 2);
+    }
+
+    #[test]
+    fn membership_and_length() {
+        let set: IntervalSet<u64> = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+        let fresh: Vec<_> = [1, 5, 8, 11, 17, 32].into_iter().filter(|id| set.contains(*id)).collect();
+        assert_eq!(fresh, vec![5, 11, 17]);
+        assert_eq!(set.covered_len(), 14);
+    }
+
+    #[test]
+    fn counts_full_ranges() {
+        let set: IntervalSet<u64> = [0..=u64::MAX].into_iter().collect();
+        assert_eq!(set.covered_len(), 1 << 64);
+        let set: IntervalSet<u128> = [0..=u128::MAX].into_iter().collect();
+        assert_eq!(set.covered_len(), u128::MAX);
+        let mut set: IntervalSet<i128> = [i128::MIN..=-1].into_iter().collect();
+        assert_eq!(set.covered_len(), 1 << 127);
+        set.insert(1..=i128::MAX);
+        assert_eq!(set.covered_len(), u128::MAX);
+        set.insert(0..=0);
+        assert_eq!(set.covered_len(), u128::MAX);
+    }
+
+    #[test]
+    fn removes_and_splits() {
+        let mut set: IntervalSet<u64> = [0..=10, 20..=30].into_iter().collect();
+        set.remove(5..=22);
+        assert_eq!(ranges(&set), vec![0..=4, 23..=30]);
+        set.remove(0..=0);
+        set.remove(30..=40);
+        assert_eq!(ranges(&set), vec![1..=4, 23..=29]);
+        assert_eq!(set.gaps().collect::<Vec<_>>(), vec![5..=22]);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: IntervalSet<i32> = [-5..=5, 10..=20].into_iter().collect();
+        let b: IntervalSet<i32> = [0..=12, 18..=25].into_iter().collect();
+        assert_eq!(a.union(&b).iter().collect::<Vec<_>>(), vec![-5..=25]);
+        assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), vec![0..=5, 10..=12, 18..=20]);
+        assert_eq!(a.difference(&b).iter().collect::<Vec<_>>(), vec![-5..=-1, 13..=17]);
+    }
+}
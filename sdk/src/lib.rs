@@ -3,9 +3,30 @@ use std::fmt::{Display, Formatter};
 pub use log::{trace, debug, info, warn, error};
 pub use winnow::{self, Parser};
 pub use anyhow;
+pub use parse::{ParseAll, ParseResultExt};
+pub use solution::{InputKind, Part, Solution};
+
+pub mod answers;
+pub mod bench;
+pub mod dial;
+pub mod dsu;
+pub mod fetch;
+pub mod frames;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod num;
+pub mod parse;
+pub mod solution;
+pub mod spatial;
+pub mod submit;
+pub mod text;
+#[cfg(test)]
+mod test_server;
 
 pub fn init() {
-    dotenv::dotenv().expect(".env not found");
+    // `.env` is optional and kept out of git, since it holds the session token. See `.env.example`.
+    let _ = dotenv::dotenv();
     pretty_env_logger::init();
 }
 
@@ -0,0 +1,331 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+pub use num_bigint::{BigInt, BigUint, ParseBigIntError};
+
+/// Integers that report overflow instead of wrapping. Implemented for the fixed-width types, which
+/// fail when a value doesn't fit, and for [BigInt], [BigUint] and [Integer], which only fail where
+/// the result isn't defined at all (a negative [BigUint], or dividing by zero).
+pub trait Number: Clone + Debug + Display + FromStr + Ord + From<u8> + Sized {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+    /// Division rounding toward zero.
+    fn checked_div(&self, other: &Self) -> Option<Self>;
+    /// Remainder with the sign of `self`, matching [Number::checked_div].
+    fn checked_rem(&self, other: &Self) -> Option<Self>;
+}
+
+macro_rules! impl_number {
+    ($($t:ty),*) => {
+        $(
+            impl Number for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *other)
+                }
+
+                fn checked_sub(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_sub(*self, *other)
+                }
+
+                fn checked_mul(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_mul(*self, *other)
+                }
+
+                fn checked_div(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_div(*self, *other)
+                }
+
+                fn checked_rem(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_rem(*self, *other)
+                }
+            }
+        )*
+    };
+}
+
+impl_number!(u32, u64, u128, usize, i32, i64, i128);
+
+impl Number for BigUint {
+    fn zero() -> Self {
+        BigUint::ZERO
+    }
+
+    fn one() -> Self {
+        BigUint::from(1_u8)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        (self >= other).then(|| self - other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        (*other != BigUint::ZERO).then(|| self / other)
+    }
+
+    fn checked_rem(&self, other: &Self) -> Option<Self> {
+        (*other != BigUint::ZERO).then(|| self % other)
+    }
+}
+
+impl Number for BigInt {
+    fn zero() -> Self {
+        BigInt::ZERO
+    }
+
+    fn one() -> Self {
+        BigInt::from(1_u8)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Some(self - other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        (*other != BigInt::ZERO).then(|| self / other)
+    }
+
+    fn checked_rem(&self, other: &Self) -> Option<Self> {
+        (*other != BigInt::ZERO).then(|| self % other)
+    }
+}
+
+/// An exact integer that stays an `i64` while it fits and moves to a [BigInt] when it doesn't, so
+/// answers of any size cost next to nothing when they're small.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Integer {
+    Small(i64),
+    /// Only ever holds values outside the `i64` range.
+    Big(BigInt),
+}
+
+impl Integer {
+    fn from_big(big: BigInt) -> Self {
+        match i64::try_from(&big) {
+            Ok(small) => Integer::Small(small),
+            Err(_) => Integer::Big(big),
+        }
+    }
+
+    fn to_big(&self) -> BigInt {
+        match self {
+            Integer::Small(small) => BigInt::from(*small),
+            Integer::Big(big) => big.clone(),
+        }
+    }
+
+    fn combine(
+        &self,
+        other: &Self,
+        small: impl FnOnce(i64, i64) -> Option<i64>,
+        big: impl FnOnce(BigInt, BigInt) -> BigInt,
+    ) -> Self {
+        if let (Integer::Small(a), Integer::Small(b)) = (self, other)
+            && let Some(result) = small(*a, *b)
+        {
+            return Integer::Small(result);
+        }
+        Integer::from_big(big(self.to_big(), other.to_big()))
+    }
+}
+
+impl Number for Integer {
+    fn zero() -> Self {
+        Integer::Small(0)
+    }
+
+    fn one() -> Self {
+        Integer::Small(1)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self.combine(other, i64::checked_add, |a, b| a + b))
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Some(self.combine(other, i64::checked_sub, |a, b| a - b))
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self.combine(other, i64::checked_mul, |a, b| a * b))
+    }
+
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        (*other != Integer::zero()).then(|| self.combine(other, i64::checked_div, |a, b| a / b))
+    }
+
+    fn checked_rem(&self, other: &Self) -> Option<Self> {
+        (*other != Integer::zero()).then(|| self.combine(other, i64::checked_rem, |a, b| a % b))
+    }
+}
+
+impl From<u8> for Integer {
+    fn from(value: u8) -> Self {
+        Integer::Small(value.into())
+    }
+}
+
+impl From<i64> for Integer {
+    fn from(value: i64) -> Self {
+        Integer::Small(value)
+    }
+}
+
+impl From<u64> for Integer {
+    fn from(value: u64) -> Self {
+        Integer::from_big(BigInt::from(value))
+    }
+}
+
+impl From<BigInt> for Integer {
+    fn from(value: BigInt) -> Self {
+        Integer::from_big(value)
+    }
+}
+
+impl Ord for Integer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Integer::Small(a), Integer::Small(b)) => a.cmp(b),
+            _ => self.to_big().cmp(&other.to_big()),
+        }
+    }
+}
+
+impl PartialOrd for Integer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Integer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Integer::Small(small) => Display::fmt(small, f),
+            Integer::Big(big) => Display::fmt(big, f),
+        }
+    }
+}
+
+impl FromStr for Integer {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse() {
+            Ok(small) => Ok(Integer::Small(small)),
+            Err(_) => s.parse().map(Integer::from_big),
+        }
+    }
+}
+
+/// An arithmetic overflow, naming the value that overflowed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    pub context: String,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Arithmetic overflow in {}", self.context)
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// Adds `value` to `total` in place. `context` describes `total`, and is only built on overflow.
+pub fn add_to<T: Number>(total: &mut T, value: &T, context: impl FnOnce() -> String) -> Result<(), Overflow> {
+    *total = total.checked_add(value).ok_or_else(|| Overflow { context: context() })?;
+    Ok(())
+}
+
+pub fn checked_sum<'a, T: Number + 'a>(values: impl IntoIterator<Item = &'a T>, context: impl FnOnce() -> String) -> Result<T, Overflow> {
+    values
+        .into_iter()
+        .try_fold(T::zero(), |sum, value| sum.checked_add(value))
+        .ok_or_else(|| Overflow { context: context() })
+}
+
+pub fn checked_product<'a, T: Number + 'a>(values: impl IntoIterator<Item = &'a T>, context: impl FnOnce() -> String) -> Result<T, Overflow> {
+    values
+        .into_iter()
+        .try_fold(T::one(), |product, value| product.checked_mul(value))
+        .ok_or_else(|| Overflow { context: context() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_fixed_width_overflow() {
+        let values = [u64::MAX / 2, u64::MAX / 2, 1];
+        assert_eq!(checked_sum(&values, || "row 3".to_owned()), Ok(u64::MAX));
+        let error = checked_product(&values, || "problem 7".to_owned()).unwrap_err();
+        assert_eq!(error.to_string(), "Arithmetic overflow in problem 7");
+        let mut total = u64::MAX;
+        assert!(add_to(&mut total, &1, || "total".to_owned()).is_err());
+        assert_eq!(total, u64::MAX);
+    }
+
+    #[test]
+    fn big_types_never_overflow() {
+        let values: Vec<BigUint> = [u64::MAX, u64::MAX].into_iter().map(BigUint::from).collect();
+        let expected = BigUint::from(u64::MAX) * BigUint::from(u64::MAX);
+        assert_eq!(checked_product(&values, String::new), Ok(expected));
+        assert_eq!(BigUint::zero().checked_sub(&BigUint::one()), None);
+    }
+
+    #[test]
+    fn integer_promotes_and_demotes() {
+        let max = Integer::from(i64::MAX);
+        let big = max.checked_add(&Integer::one()).unwrap();
+        assert!(matches!(big, Integer::Big(_)));
+        assert_eq!(big.to_string(), "9223372036854775808");
+        assert_eq!(big.checked_sub(&Integer::one()), Some(max.clone()));
+        assert!(big > max);
+        let squared = checked_product(&[big.clone(), big.clone()], String::new).unwrap();
+        assert_eq!(squared.to_string(), "85070591730234615865843651857942052864");
+        assert_eq!("85070591730234615865843651857942052864".parse(), Ok(squared));
+        assert_eq!("-12".parse(), Ok(Integer::Small(-12)));
+        assert_eq!(Integer::from(u64::MAX).to_string(), u64::MAX.to_string());
+    }
+
+    #[test]
+    fn divides_toward_zero() {
+        let min = Integer::from(i64::MIN);
+        let minus_one = Integer::from(-1_i64);
+        assert_eq!(min.checked_div(&minus_one).unwrap().to_string(), "9223372036854775808");
+        assert_eq!(min.checked_rem(&minus_one), Some(Integer::zero()));
+        assert_eq!(Integer::from(-7_i64).checked_div(&Integer::from(2_i64)), Some(Integer::from(-3_i64)));
+        assert_eq!(Integer::from(-7_i64).checked_rem(&Integer::from(2_i64)), Some(Integer::from(-1_i64)));
+        assert_eq!(Integer::one().checked_div(&Integer::zero()), None);
+        assert_eq!(BigUint::one().checked_rem(&BigUint::zero()), None);
+        assert_eq!(Number::checked_div(&7_u64, &0), None);
+    }
+}
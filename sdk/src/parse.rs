@@ -0,0 +1,144 @@
+use std::fmt::{Display, Formatter};
+use winnow::ascii::multispace0;
+use winnow::combinator::{eof, terminated};
+use winnow::error::{ContextError, ParseError, StrContext, StrContextValue};
+use winnow::Parser;
+
+/// A parse failure located in the original input: line and column (both 1-based), the offending
+/// line, and whatever context the parser attached.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub labels: Vec<String>,
+    pub expected: Vec<String>,
+    pub cause: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(input: &str, offset: usize, error: &ContextError) -> Self {
+        let mut labels = Vec::new();
+        let mut expected = Vec::new();
+        for context in error.context() {
+            match context {
+                StrContext::Label(label) => labels.push(label.to_string()),
+                StrContext::Expected(value) => expected.push(value.to_string()),
+                _ => {}
+            }
+        }
+        Diagnostic {
+            labels,
+            expected,
+            cause: error.cause().map(|cause| cause.to_string()),
+            ..Diagnostic::at(input, offset, "")
+        }
+    }
+
+    /// A diagnostic for a failure found outside winnow, such as a check on already split input.
+    pub fn at(input: &str, offset: usize, cause: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = input[offset..].find('\n').map(|i| offset + i).unwrap_or(input.len());
+        let cause = cause.into();
+        Diagnostic {
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end].trim_end_matches('\r').to_owned(),
+            labels: Vec::new(),
+            expected: Vec::new(),
+            cause: (!cause.is_empty()).then_some(cause),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Parse error at line {}, column {}", self.line, self.column)?;
+        if !self.labels.is_empty() {
+            write!(f, " while parsing {}", self.labels.join(" in "))?;
+        }
+        if !self.expected.is_empty() {
+            write!(f, ": expected {}", self.expected.join(" or "))?;
+        }
+        if let Some(cause) = &self.cause {
+            write!(f, ": {cause}")?;
+        }
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{gutter} | {}^", " ".repeat(self.column - 1))
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+/// Converts winnow's [ParseError] into a located [Diagnostic].
+pub trait ParseResultExt<O> {
+    fn located(self, input: &str) -> Result<O, Diagnostic>;
+}
+
+impl<O> ParseResultExt<O> for Result<O, ParseError<&str, ContextError>> {
+    fn located(self, input: &str) -> Result<O, Diagnostic> {
+        self.map_err(|e| Diagnostic::new(input, e.offset(), e.inner()))
+    }
+}
+
+pub trait ParseAll<'i, O> {
+    /// Runs the parser over the whole input, allowing only trailing whitespace to be left over.
+    fn parse_all(&mut self, input: &'i str) -> Result<O, Diagnostic>;
+}
+
+impl<'i, O, P> ParseAll<'i, O> for P
+where
+    P: Parser<&'i str, O, ContextError>,
+{
+    fn parse_all(&mut self, input: &'i str) -> Result<O, Diagnostic> {
+        let end = (multispace0, eof).context(StrContext::Expected(StrContextValue::Description("end of input")));
+        terminated(self.by_ref(), end).parse(input).located(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winnow::ascii::dec_uint;
+    use winnow::combinator::separated;
+
+    fn numbers(input: &mut &str) -> winnow::Result<Vec<u32>> {
+        separated(1.., dec_uint::<_, u32, _>, '\n')
+            .context(StrContext::Label("numbers"))
+            .context(StrContext::Expected(StrContextValue::Description("a number per line")))
+            .parse_next(input)
+    }
+
+    #[test]
+    fn allows_trailing_whitespace() {
+        assert_eq!(numbers.parse_all("1\n2\n3\n").unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn locates_unconsumed_input() {
+        let error = numbers.parse_all("1\n22\n3x3\n4").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 2));
+        assert_eq!(error.snippet, "3x3");
+        assert_eq!(error.expected, vec!["end of input"]);
+        assert!(error.to_string().ends_with("3 | 3x3\n  |  ^"));
+    }
+
+    #[test]
+    fn locates_custom_errors() {
+        let error = Diagnostic::at("12\n3 4\n", 4, "gap in number");
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.to_string(), "Parse error at line 2, column 2: gap in number\n  |\n2 | 3 4\n  |  ^");
+    }
+
+    #[test]
+    fn reports_context() {
+        let error = numbers.parse_all("x").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.labels, vec!["numbers"]);
+        assert_eq!(error.expected, vec!["a number per line"]);
+    }
+}
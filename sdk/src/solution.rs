@@ -0,0 +1,172 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use log::{error, info};
+use crate::answers::Answers;
+use crate::bench::{bench, BenchConfig, BenchReport};
+use crate::input::Inputs;
+use crate::TextError;
+
+/// A single day's puzzle: parse the input once, then solve each part against the parsed value.
+pub trait Solution {
+    const DAY: u8;
+    type Input;
+    type Output1: Display;
+    type Output2: Display;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input>;
+
+    /// Parses input of a known kind. Days whose example uses different parameters from the real
+    /// puzzle (a smaller iteration count, say) override this; by default the kind is ignored.
+    fn parse_for(input: &str, _kind: InputKind) -> anyhow::Result<Self::Input> {
+        Self::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> anyhow::Result<Self::Output1>;
+
+    fn part2(input: &Self::Input) -> anyhow::Result<Self::Output2>;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum InputKind {
+    Example,
+    Real,
+}
+
+impl InputKind {
+    pub const ALL: &'static [InputKind] = &[InputKind::Example, InputKind::Real];
+}
+
+impl Display for InputKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputKind::Example => f.pad("example"),
+            InputKind::Real => f.pad("real"),
+        }
+    }
+}
+
+impl FromStr for InputKind {
+    type Err = TextError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "example" => Ok(InputKind::Example),
+            "real" | "input" => Ok(InputKind::Real),
+            other => Err(TextError(format!("Unknown input kind: {other}"))),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: &'static [Part] = &[Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = TextError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            other => Err(TextError(format!("Unknown part: {other}"))),
+        }
+    }
+}
+
+/// Entry point for a day's binary: initializes logging and runs both parts against the example
+/// and real inputs.
+pub fn main<S: Solution>() -> anyhow::Result<()> {
+    crate::init();
+    let inputs = Inputs::from_env();
+    let answers = Answers::load(&inputs, S::DAY)?;
+    for kind in InputKind::ALL {
+        let input = inputs.load(S::DAY, *kind)?;
+        run::<S>(*kind, &input, Part::ALL, &answers)?;
+    }
+    Ok(())
+}
+
+/// Parses the input and runs the requested parts, checking each output against the expected
+/// answers when one is recorded. A failing part is logged rather than aborting the run, so an
+/// unsolved part 2 doesn't hide the part 1 answer.
+pub fn run<S: Solution>(kind: InputKind, input: &str, parts: &[Part], answers: &Answers) -> anyhow::Result<()> {
+    let parsed = S::parse_for(input, kind)?;
+    for part in parts {
+        let output = match solve_parsed::<S>(&parsed, *part) {
+            Ok(output) => output,
+            Err(e) => {
+                error!("Day {} part {part} {kind} failed: {e}", S::DAY);
+                continue;
+            }
+        };
+        match answers.expected(kind, *part) {
+            Some(expected) if expected == output => {
+                info!("Day {} part {part} {kind} output: {output} (pass)", S::DAY);
+            }
+            Some(expected) => {
+                error!("Day {} part {part} {kind} output: {output} (FAIL, expected {expected})", S::DAY);
+            }
+            None => info!("Day {} part {part} {kind} output: {output}", S::DAY),
+        }
+    }
+    Ok(())
+}
+
+/// Parses the input and returns the answer to one part.
+pub fn solve<S: Solution>(kind: InputKind, input: &str, part: Part) -> anyhow::Result<String> {
+    solve_parsed::<S>(&S::parse_for(input, kind)?, part)
+}
+
+fn solve_parsed<S: Solution>(parsed: &S::Input, part: Part) -> anyhow::Result<String> {
+    match part {
+        Part::One => S::part1(parsed).map(|output| output.to_string()),
+        Part::Two => S::part2(parsed).map(|output| output.to_string()),
+    }
+}
+
+/// A type-erased [Solution], so days can be collected and dispatched at runtime.
+#[derive(Copy, Clone)]
+pub struct Registration {
+    pub day: u8,
+    run: fn(InputKind, &str, &[Part], &Answers) -> anyhow::Result<()>,
+    solve: fn(InputKind, &str, Part) -> anyhow::Result<String>,
+    bench: fn(InputKind, &str, &[Part], BenchConfig) -> anyhow::Result<BenchReport>,
+}
+
+impl Registration {
+    pub fn new<S: Solution>() -> Self {
+        Registration {
+            day: S::DAY,
+            run: run::<S>,
+            solve: solve::<S>,
+            bench: bench::<S>,
+        }
+    }
+
+    pub fn run(&self, kind: InputKind, input: &str, parts: &[Part], answers: &Answers) -> anyhow::Result<()> {
+        (self.run)(kind, input, parts, answers)
+    }
+
+    pub fn solve(&self, kind: InputKind, input: &str, part: Part) -> anyhow::Result<String> {
+        (self.solve)(kind, input, part)
+    }
+
+    pub fn bench(&self, kind: InputKind, input: &str, parts: &[Part], config: BenchConfig) -> anyhow::Result<BenchReport> {
+        (self.bench)(kind, input, parts, config)
+    }
+}
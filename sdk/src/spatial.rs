@@ -0,0 +1,300 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::{Display, Formatter};
+
+/// A point in 3D integer space. Distances are compared squared, so they stay exact.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Point3 { x, y, z }
+    }
+
+    /// Exact unless the points are at least `2^64` apart, where it saturates at `u128::MAX`: each
+    /// squared difference fits, but the sum of three might not.
+    pub fn distance_squared(&self, other: &Point3) -> u128 {
+        let dx = self.x.abs_diff(other.x) as u128;
+        let dy = self.y.abs_diff(other.y) as u128;
+        let dz = self.z.abs_diff(other.z) as u128;
+        (dx * dx).saturating_add(dy * dy).saturating_add(dz * dz)
+    }
+
+    /// The coordinate along `axis`: 0 is x, 1 is y, 2 is z.
+    pub fn axis(&self, axis: usize) -> i64 {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            2 => self.z,
+            _ => panic!("Point3 has no axis {axis}"),
+        }
+    }
+}
+
+impl Display for Point3 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// A static k-d tree over a set of points. Queries return indices into the slice the tree was built
+/// from, paired with the squared distance. Results are ordered by distance, ties by index, so
+/// asking for more neighbors only ever extends the previous answer.
+#[derive(Debug, Clone)]
+pub struct KdTree {
+    points: Vec<Point3>,
+    /// Point indices laid out so each subrange's median splits it on the axis for its depth.
+    order: Vec<usize>,
+}
+
+impl KdTree {
+    pub fn new(points: &[Point3]) -> Self {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        build(points, &mut order, 0);
+        KdTree { points: points.to_vec(), order }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn points(&self) -> &[Point3] {
+        &self.points
+    }
+
+    /// The point closest to `query`, which may be `query` itself if it's in the tree.
+    pub fn nearest(&self, query: &Point3) -> Option<(usize, u128)> {
+        self.k_nearest(query, 1).into_iter().next()
+    }
+
+    /// Up to `k` points closest to `query`, closest first.
+    pub fn k_nearest(&self, query: &Point3, k: usize) -> Vec<(usize, u128)> {
+        self.k_nearest_by(query, k, |_| true)
+    }
+
+    /// Up to `k` points closest to `query` among those whose index passes `keep`, closest first.
+    pub fn k_nearest_by(&self, query: &Point3, k: usize, keep: impl Fn(usize) -> bool) -> Vec<(usize, u128)> {
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.search(&self.order, 0, query, k, &keep, &mut best);
+        }
+        let mut found: Vec<_> = best.into_iter().map(|(distance, index)| (index, distance)).collect();
+        found.sort_unstable_by_key(|&(index, distance)| (distance, index));
+        found
+    }
+
+    /// Every point within `radius_squared` of `query` (inclusive), closest first.
+    pub fn within(&self, query: &Point3, radius_squared: u128) -> Vec<(usize, u128)> {
+        let mut found = Vec::new();
+        self.collect_within(&self.order, 0, query, radius_squared, &mut found);
+        found.sort_unstable_by_key(|&(index, distance)| (distance, index));
+        found
+    }
+
+    /// Every pair of distinct points, closest first, computed lazily. Pairs are `(a, b, distance)`
+    /// with `a < b`, and ties are ordered by `a` then `b`.
+    pub fn closest_pairs(&self) -> ClosestPairs<'_> {
+        let mut pairs = ClosestPairs { tree: self, cursors: Vec::with_capacity(self.len()), heap: BinaryHeap::new() };
+        for index in 0..self.len() {
+            pairs.cursors.push(Cursor { found: Vec::new(), next: 0, exhausted: false });
+            pairs.advance(index);
+        }
+        pairs
+    }
+
+    fn search(
+        &self,
+        order: &[usize],
+        depth: usize,
+        query: &Point3,
+        k: usize,
+        keep: &impl Fn(usize) -> bool,
+        best: &mut BinaryHeap<(u128, usize)>,
+    ) {
+        if order.is_empty() {
+            return;
+        }
+        let mid = order.len() / 2;
+        let index = order[mid];
+        let point = &self.points[index];
+        if keep(index) {
+            best.push((query.distance_squared(point), index));
+            if best.len() > k {
+                best.pop();
+            }
+        }
+        let axis = depth % 3;
+        let (near, far) = if query.axis(axis) < point.axis(axis) {
+            (&order[..mid], &order[mid + 1..])
+        } else {
+            (&order[mid + 1..], &order[..mid])
+        };
+        self.search(near, depth + 1, query, k, keep, best);
+        let plane = query.axis(axis).abs_diff(point.axis(axis)) as u128;
+        // Only skip the far side when the plane is strictly beyond the worst kept distance: an
+        // equally distant point with a lower index could still displace it
+        if best.len() < k || best.peek().is_some_and(|&(worst, _)| plane * plane <= worst) {
+            self.search(far, depth + 1, query, k, keep, best);
+        }
+    }
+
+    fn collect_within(&self, order: &[usize], depth: usize, query: &Point3, radius_squared: u128, found: &mut Vec<(usize, u128)>) {
+        if order.is_empty() {
+            return;
+        }
+        let mid = order.len() / 2;
+        let index = order[mid];
+        let point = &self.points[index];
+        let distance = query.distance_squared(point);
+        if distance <= radius_squared {
+            found.push((index, distance));
+        }
+        let axis = depth % 3;
+        let plane = query.axis(axis).abs_diff(point.axis(axis)) as u128;
+        let reaches_plane = plane * plane <= radius_squared;
+        if query.axis(axis) < point.axis(axis) || reaches_plane {
+            self.collect_within(&order[..mid], depth + 1, query, radius_squared, found);
+        }
+        if query.axis(axis) >= point.axis(axis) || reaches_plane {
+            self.collect_within(&order[mid + 1..], depth + 1, query, radius_squared, found);
+        }
+    }
+}
+
+fn build(points: &[Point3], order: &mut [usize], depth: usize) {
+    if order.len() <= 1 {
+        return;
+    }
+    let axis = depth % 3;
+    let mid = order.len() / 2;
+    order.select_nth_unstable_by_key(mid, |&i| points[i].axis(axis));
+    let (left, right) = order.split_at_mut(mid);
+    build(points, left, depth + 1);
+    build(points, &mut right[1..], depth + 1);
+}
+
+/// Iterator over a [KdTree]'s pairs of points in order of distance; see [KdTree::closest_pairs].
+///
+/// Each point keeps a cursor over its neighbors with a higher index, fetched in doubling batches,
+/// and a heap holds every point's next unvisited neighbor. Taking the first `n` pairs costs roughly
+/// `n log n` rather than computing all `len²/2` distances up front.
+#[derive(Debug)]
+pub struct ClosestPairs<'t> {
+    tree: &'t KdTree,
+    cursors: Vec<Cursor>,
+    heap: BinaryHeap<Reverse<(u128, usize, usize)>>,
+}
+
+#[derive(Debug)]
+struct Cursor {
+    found: Vec<(usize, u128)>,
+    next: usize,
+    exhausted: bool,
+}
+
+impl ClosestPairs<'_> {
+    /// Queues the next neighbor of `index`, fetching a bigger batch when the current one runs out.
+    fn advance(&mut self, index: usize) {
+        let cursor = &mut self.cursors[index];
+        if cursor.next == cursor.found.len() && !cursor.exhausted {
+            let k = (cursor.found.len() * 2).max(4);
+            let point = self.tree.points[index];
+            cursor.found = self.tree.k_nearest_by(&point, k, |other| other > index);
+            cursor.exhausted = cursor.found.len() < k;
+        }
+        if let Some(&(other, distance)) = cursor.found.get(cursor.next) {
+            cursor.next += 1;
+            self.heap.push(Reverse((distance, index, other)));
+        }
+    }
+}
+
+impl Iterator for ClosestPairs<'_> {
+    type Item = (usize, usize, u128);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((distance, a, b)) = self.heap.pop()?;
+        self.advance(a);
+        Some((a, b, distance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random points, so the brute-force comparisons are reproducible.
+    fn points(count: usize, span: i64) -> Vec<Point3> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % span as u64) as i64 - span / 2
+        };
+        (0..count).map(|_| Point3::new(next(), next(), next())).collect()
+    }
+
+    fn brute_force(points: &[Point3], query: &Point3) -> Vec<(usize, u128)> {
+        let mut all: Vec<_> = points.iter().enumerate().map(|(i, p)| (i, query.distance_squared(p))).collect();
+        all.sort_unstable_by_key(|&(index, distance)| (distance, index));
+        all
+    }
+
+    #[test]
+    fn nearest_and_k_nearest_match_brute_force() {
+        // A small span forces plenty of ties and duplicate points
+        let points = points(500, 20);
+        let tree = KdTree::new(&points);
+        for query in self::points(50, 30) {
+            let expected = brute_force(&points, &query);
+            assert_eq!(tree.nearest(&query), expected.first().copied());
+            assert_eq!(tree.k_nearest(&query, 7), expected[..7]);
+        }
+        assert_eq!(tree.k_nearest(&Point3::default(), 1000).len(), 500);
+        assert!(KdTree::new(&[]).nearest(&Point3::default()).is_none());
+    }
+
+    #[test]
+    fn saturates_extreme_distances() {
+        let (min, max) = (Point3::new(i64::MIN, i64::MIN, i64::MIN), Point3::new(i64::MAX, i64::MAX, i64::MAX));
+        assert_eq!(min.distance_squared(&max), u128::MAX);
+        assert_eq!(Point3::new(i64::MIN, 0, 0).distance_squared(&Point3::new(i64::MAX, 0, 0)), u128::from(u64::MAX).pow(2));
+        let points = [min, max, Point3::default(), Point3::new(i64::MAX, i64::MIN, 0)];
+        let tree = KdTree::new(&points);
+        assert_eq!(tree.nearest(&max), Some((1, 0)));
+        assert_eq!(tree.k_nearest(&min, 4), brute_force(&points, &min));
+        assert_eq!(KdTree::new(&points).closest_pairs().count(), 6);
+    }
+
+    #[test]
+    fn radius_matches_brute_force() {
+        let points = points(500, 100);
+        let tree = KdTree::new(&points);
+        for query in self::points(20, 120) {
+            let expected: Vec<_> = brute_force(&points, &query).into_iter().filter(|&(_, d)| d <= 400).collect();
+            assert_eq!(tree.within(&query, 400), expected);
+        }
+    }
+
+    #[test]
+    fn closest_pairs_match_brute_force() {
+        let points = points(200, 50);
+        let mut expected: Vec<_> = (0..points.len())
+            .flat_map(|a| (a + 1..points.len()).map(move |b| (a, b)))
+            .map(|(a, b)| (points[a].distance_squared(&points[b]), a, b))
+            .collect();
+        expected.sort_unstable();
+        let expected: Vec<_> = expected.into_iter().map(|(d, a, b)| (a, b, d)).collect();
+        let actual: Vec<_> = KdTree::new(&points).closest_pairs().collect();
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,288 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use anyhow::{bail, Context};
+use log::{info, warn};
+use crate::fetch::Client;
+use crate::input::Inputs;
+use crate::{Part, TextError};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    WrongAnswer,
+    /// The site asked us to wait this long before submitting again.
+    RateLimited(Duration),
+    /// The part was already solved, so the answer wasn't checked.
+    AlreadyComplete,
+}
+
+impl Outcome {
+    /// Whether this outcome is a verdict on the answer itself, as opposed to the submission being
+    /// turned away.
+    pub fn is_verdict(&self) -> bool {
+        matches!(self, Outcome::Correct | Outcome::TooHigh | Outcome::TooLow | Outcome::WrongAnswer)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "too-high"),
+            Outcome::TooLow => write!(f, "too-low"),
+            Outcome::WrongAnswer => write!(f, "wrong"),
+            Outcome::RateLimited(wait) => write!(f, "rate-limited:{}", wait.as_secs()),
+            Outcome::AlreadyComplete => write!(f, "already-complete"),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = TextError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "too-high" => Ok(Outcome::TooHigh),
+            "too-low" => Ok(Outcome::TooLow),
+            "wrong" => Ok(Outcome::WrongAnswer),
+            "already-complete" => Ok(Outcome::AlreadyComplete),
+            other => other
+                .strip_prefix("rate-limited:")
+                .and_then(|secs| secs.parse().ok())
+                .map(|secs| Outcome::RateLimited(Duration::from_secs(secs)))
+                .ok_or_else(|| TextError(format!("Unknown outcome: {other}"))),
+        }
+    }
+}
+
+/// Classifies the page returned after posting an answer.
+pub fn parse_response(html: &str) -> anyhow::Result<Outcome> {
+    let text = article_text(html);
+    let outcome = if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("your answer is too high") {
+        Outcome::TooHigh
+    } else if text.contains("your answer is too low") {
+        Outcome::TooLow
+    } else if text.contains("That's not the right answer") {
+        Outcome::WrongAnswer
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::RateLimited(parse_wait(&text).unwrap_or(Duration::from_secs(60)))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadyComplete
+    } else {
+        bail!("Unrecognized submission response: {}", text.trim());
+    };
+    Ok(outcome)
+}
+
+/// The text of the page's `<article>` (or the whole page if there isn't one), with tags removed.
+fn article_text(html: &str) -> String {
+    let article = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    let mut text = String::with_capacity(article.len());
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Parses "You have 1m 23s left to wait" style messages.
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+    let mut secs = 0;
+    for token in text[start..end].split_whitespace() {
+        if let Some(minutes) = token.strip_suffix('m') {
+            secs += minutes.parse::<u64>().ok()? * 60;
+        } else if let Some(seconds) = token.strip_suffix('s') {
+            secs += seconds.parse::<u64>().ok()?;
+        } else {
+            return None;
+        }
+    }
+    Some(Duration::from_secs(secs))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub part: Part,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+/// Every answer submitted for a day, stored as tab-separated lines next to the day's inputs in a
+/// git-ignored `submissions.tsv`.
+#[derive(Debug)]
+pub struct History {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+impl History {
+    pub fn load(inputs: &Inputs, day: u8) -> anyhow::Result<Self> {
+        let path = inputs.dir().join(format!("day{day}")).join("submissions.tsv");
+        let submissions = if path.is_file() {
+            let text = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            text.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(parse_submission)
+                .collect::<anyhow::Result<_>>()
+                .with_context(|| format!("Malformed submission history in {}", path.display()))?
+        } else {
+            Vec::new()
+        };
+        Ok(History { path, submissions })
+    }
+
+    pub fn submissions(&self) -> &[Submission] {
+        &self.submissions
+    }
+
+    /// The verdict we already know for this answer, either because it was submitted before or
+    /// because it falls outside bounds established by earlier too high / too low answers.
+    pub fn known_outcome(&self, part: Part, answer: &str) -> Option<Outcome> {
+        let verdicts = self.submissions.iter().filter(|s| s.part == part && s.outcome.is_verdict());
+        if let Some(correct) = verdicts.clone().find(|s| s.outcome == Outcome::Correct) {
+            return Some(if correct.answer == answer { Outcome::Correct } else { Outcome::WrongAnswer });
+        }
+        if let Some(previous) = verdicts.clone().find(|s| s.answer == answer) {
+            return Some(previous.outcome);
+        }
+        let value: i128 = answer.parse().ok()?;
+        for previous in verdicts {
+            let Ok(bound) = previous.answer.parse::<i128>() else {
+                continue;
+            };
+            match previous.outcome {
+                Outcome::TooHigh if value >= bound => return Some(Outcome::TooHigh),
+                Outcome::TooLow if value <= bound => return Some(Outcome::TooLow),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    pub fn record(&mut self, submission: Submission) -> anyhow::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        self.submissions.push(submission);
+        let text: String = self.submissions
+            .iter()
+            .map(|s| format!("{}\t{}\t{}\n", s.part, s.answer, s.outcome))
+            .collect();
+        fs::write(&self.path, text).with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
+fn parse_submission(line: &str) -> anyhow::Result<Submission> {
+    let mut fields = line.split('\t');
+    let (Some(part), Some(answer), Some(outcome), None) = (fields.next(), fields.next(), fields.next(), fields.next()) else {
+        bail!("Expected `part<TAB>answer<TAB>outcome`, got {line:?}");
+    };
+    Ok(Submission {
+        part: part.parse()?,
+        answer: answer.to_owned(),
+        outcome: outcome.parse()?,
+    })
+}
+
+/// Submits an answer unless the history already knows how it will be judged, and records the
+/// outcome of anything actually sent.
+pub fn submit(client: &Client, inputs: &Inputs, day: u8, part: Part, answer: &str) -> anyhow::Result<Outcome> {
+    let answer = answer.trim();
+    if answer.is_empty() {
+        bail!("Refusing to submit an empty answer");
+    }
+    let mut history = History::load(inputs, day)?;
+    if let Some(outcome) = history.known_outcome(part, answer) {
+        warn!("Not resubmitting day {day} part {part} answer {answer}, already known to be {outcome}");
+        return Ok(outcome);
+    }
+    let level = part.to_string();
+    let response = client.post_form(&format!("/day/{day}/answer"), &[("level", &level), ("answer", answer)])?;
+    let outcome = parse_response(&response)?;
+    info!("Day {day} part {part} answer {answer}: {outcome}");
+    history.record(Submission { part, answer: answer.to_owned(), outcome })?;
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::{temp_dir, TestServer};
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main><article><p>{message}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn parses_responses() {
+        let cases = [
+            ("That's the right answer! You are one gold star closer.", Outcome::Correct),
+            ("That's not the right answer; your answer is too high.", Outcome::TooHigh),
+            ("That's not the right answer; your answer is too low.", Outcome::TooLow),
+            ("That's not the right answer. If you're stuck, <a href=\"/\">ask</a>.", Outcome::WrongAnswer),
+            ("You gave an answer too recently. You have 1m 5s left to wait.", Outcome::RateLimited(Duration::from_secs(65))),
+            ("You gave an answer too recently. You have 34s left to wait.", Outcome::RateLimited(Duration::from_secs(34))),
+            ("You don't seem to be solving the right level. Did you already complete it?", Outcome::AlreadyComplete),
+        ];
+        for (message, expected) in cases {
+            assert_eq!(parse_response(&page(message)).unwrap(), expected, "{message}");
+        }
+        assert!(parse_response(&page("Something else entirely")).is_err());
+    }
+
+    #[test]
+    fn submits_and_records_answers() {
+        let server = TestServer::start(vec![
+            (200, page("That's not the right answer; your answer is too low.")),
+            (200, page("That's the right answer!")),
+        ]);
+        let inputs = Inputs::new(temp_dir("submit"));
+        let client = Client::new(server.url(), "secret", "test-agent").with_min_interval(Duration::ZERO);
+
+        assert_eq!(submit(&client, &inputs, 7, Part::Two, "100").unwrap(), Outcome::TooLow);
+        assert_eq!(submit(&client, &inputs, 7, Part::Two, "150").unwrap(), Outcome::Correct);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].to_lowercase().starts_with("post /2025/day/7/answer "));
+        assert!(requests[0].ends_with("level=2&answer=100"));
+
+        let history = History::load(&inputs, 7).unwrap();
+        assert_eq!(history.submissions().len(), 2);
+        assert_eq!(history.submissions()[1].outcome, Outcome::Correct);
+    }
+
+    #[test]
+    fn never_resubmits_known_answers() {
+        // No responses queued: any request reaching the server would fail the test
+        let server = TestServer::start(Vec::new());
+        let inputs = Inputs::new(temp_dir("submit-known"));
+        let client = Client::new(server.url(), "secret", "test-agent");
+        let mut history = History::load(&inputs, 1).unwrap();
+        history.record(Submission { part: Part::One, answer: "500".to_owned(), outcome: Outcome::TooHigh }).unwrap();
+        history.record(Submission { part: Part::One, answer: "42".to_owned(), outcome: Outcome::WrongAnswer }).unwrap();
+
+        assert_eq!(submit(&client, &inputs, 1, Part::One, "42").unwrap(), Outcome::WrongAnswer);
+        assert_eq!(submit(&client, &inputs, 1, Part::One, "501").unwrap(), Outcome::TooHigh);
+        assert!(server.requests().is_empty());
+    }
+}
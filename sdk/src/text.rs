@@ -0,0 +1,55 @@
+use crate::grid::Grid;
+
+/// Lays lines out as a rectangle of characters, padding short lines on the right with spaces.
+pub fn to_grid<S: AsRef<str>>(lines: &[S]) -> Grid<char> {
+    let width = lines.iter().map(|line| line.as_ref().chars().count()).max().unwrap_or(0);
+    let rows = lines
+        .iter()
+        .map(|line| {
+            let mut row: Vec<char> = line.as_ref().chars().collect();
+            row.resize(width, ' ');
+            row
+        })
+        .collect();
+    Grid::new(rows).expect("rows are padded to the same width")
+}
+
+pub fn from_grid(grid: &Grid<char>) -> Vec<String> {
+    grid.rows().map(|row| row.iter().collect()).collect()
+}
+
+/// Columns become lines: line `i` of the result is column `i` read top to bottom.
+pub fn transpose<S: AsRef<str>>(lines: &[S]) -> Vec<String> {
+    from_grid(&to_grid(lines).transpose())
+}
+
+/// Line `i` of the result is column `i` read bottom to top.
+pub fn rotate_clockwise<S: AsRef<str>>(lines: &[S]) -> Vec<String> {
+    from_grid(&to_grid(lines).rotate_clockwise())
+}
+
+/// Line `i` of the result is column `i` from the right, read top to bottom.
+pub fn rotate_counterclockwise<S: AsRef<str>>(lines: &[S]) -> Vec<String> {
+    from_grid(&to_grid(lines).rotate_counterclockwise())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLOCK: [&str; 3] = ["64", "23", "314"];
+
+    #[test]
+    fn pads_ragged_lines() {
+        assert_eq!(from_grid(&to_grid(&BLOCK)), vec!["64 ", "23 ", "314"]);
+        assert!(transpose::<&str>(&[]).is_empty());
+    }
+
+    #[test]
+    fn transposes_and_rotates() {
+        assert_eq!(transpose(&BLOCK), vec!["623", "431", "  4"]);
+        assert_eq!(rotate_clockwise(&BLOCK), vec!["326", "134", "4  "]);
+        assert_eq!(rotate_counterclockwise(&BLOCK), vec!["  4", "431", "623"]);
+        assert_eq!(transpose(&transpose(&BLOCK)), vec!["64 ", "23 ", "314"]);
+    }
+}